@@ -2,4 +2,20 @@
 
 pub mod prelude;
 
+pub mod calendar_feed;
+pub mod job;
+pub mod notification;
+pub mod outbox;
+pub mod reminder_delivery;
+pub mod saved_filter;
+pub mod sea_orm_active_enums;
+pub mod share_link;
+pub mod time_entry;
 pub mod todo;
+pub mod todo_comment;
+pub mod todo_dependency;
+pub mod todo_tag;
+pub mod todo_template;
+pub mod todo_template_item;
+pub mod todo_tombstone;
+pub mod user_email;
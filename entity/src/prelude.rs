@@ -1,3 +1,18 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+pub use super::calendar_feed::Entity as CalendarFeed;
+pub use super::job::Entity as Job;
+pub use super::notification::Entity as Notification;
+pub use super::outbox::Entity as Outbox;
+pub use super::reminder_delivery::Entity as ReminderDelivery;
+pub use super::saved_filter::Entity as SavedFilter;
+pub use super::share_link::Entity as ShareLink;
+pub use super::time_entry::Entity as TimeEntry;
 pub use super::todo::Entity as Todo;
+pub use super::todo_comment::Entity as TodoComment;
+pub use super::todo_dependency::Entity as TodoDependency;
+pub use super::todo_tag::Entity as TodoTag;
+pub use super::todo_template::Entity as TodoTemplate;
+pub use super::todo_template_item::Entity as TodoTemplateItem;
+pub use super::todo_tombstone::Entity as TodoTombstone;
+pub use super::user_email::Entity as UserEmail;
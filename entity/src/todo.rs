@@ -1,5 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+use super::sea_orm_active_enums::TodoPriority;
+use super::sea_orm_active_enums::TodoStatus;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -8,9 +10,28 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub title: String,
-    pub completed: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub change_seq: i64,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    pub archived_at: Option<DateTimeWithTimeZone>,
+    pub status: TodoStatus,
+    pub project_id: Option<Uuid>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub owner_id: Option<String>,
+    pub due_at: Option<DateTimeWithTimeZone>,
+    pub priority: Option<TodoPriority>,
+    pub tracked_seconds: i64,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub assignee_id: Option<String>,
+    pub remind_at: Option<DateTimeWithTimeZone>,
+    pub reminded_at: Option<DateTimeWithTimeZone>,
+    pub parent_id: Option<Uuid>,
+    pub position: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    /// Sequential per owner, assigned by a database trigger on insert.
+    pub number: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
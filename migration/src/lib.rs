@@ -1,12 +1,46 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20220101_000002_create_outbox_table;
+mod m20220101_000003_add_todo_change_tracking;
+mod m20220101_000004_create_job_table;
+mod m20220101_000005_add_todo_archiving;
+mod m20220101_000006_add_todo_status;
+mod m20220101_000007_create_todo_dependency_table;
+mod m20220101_000008_create_calendar_feed_table;
+mod m20220101_000009_add_todo_priority_and_tags;
+mod m20220101_000010_create_time_entry_table;
+mod m20220101_000011_create_saved_filter_table;
+mod m20220101_000012_create_share_link_table;
+mod m20220101_000013_create_notification_table;
+mod m20220101_000014_add_todo_reminders;
+mod m20220101_000015_create_todo_template_table;
+mod m20220101_000016_add_todo_description;
+mod m20220101_000017_add_todo_number;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20220101_000002_create_outbox_table::Migration),
+            Box::new(m20220101_000003_add_todo_change_tracking::Migration),
+            Box::new(m20220101_000004_create_job_table::Migration),
+            Box::new(m20220101_000005_add_todo_archiving::Migration),
+            Box::new(m20220101_000006_add_todo_status::Migration),
+            Box::new(m20220101_000007_create_todo_dependency_table::Migration),
+            Box::new(m20220101_000008_create_calendar_feed_table::Migration),
+            Box::new(m20220101_000009_add_todo_priority_and_tags::Migration),
+            Box::new(m20220101_000010_create_time_entry_table::Migration),
+            Box::new(m20220101_000011_create_saved_filter_table::Migration),
+            Box::new(m20220101_000012_create_share_link_table::Migration),
+            Box::new(m20220101_000013_create_notification_table::Migration),
+            Box::new(m20220101_000014_add_todo_reminders::Migration),
+            Box::new(m20220101_000015_create_todo_template_table::Migration),
+            Box::new(m20220101_000016_add_todo_description::Migration),
+            Box::new(m20220101_000017_add_todo_number::Migration),
+        ]
     }
 }
@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Outbox {
+    Table,
+    Id,
+    AggregateType,
+    AggregateId,
+    EventType,
+    Payload,
+    Attempts,
+    LastError,
+    CreatedAt,
+    DeliveredAt,
+    NextAttemptAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Outbox::Table)
+                    .if_not_exists()
+                    .col(uuid(Outbox::Id).not_null().primary_key().default(Expr::cust("gen_random_uuid()")))
+                    .col(string(Outbox::AggregateType).not_null())
+                    .col(uuid(Outbox::AggregateId).not_null())
+                    .col(string(Outbox::EventType).not_null())
+                    .col(json_binary(Outbox::Payload).not_null())
+                    .col(integer(Outbox::Attempts).not_null().default(0))
+                    .col(text_null(Outbox::LastError))
+                    .col(
+                        timestamp_with_time_zone(Outbox::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null(Outbox::DeliveredAt))
+                    // Pushed forward while a relay holds the event and after
+                    // each failed attempt, so claimed and backing-off events
+                    // are skipped.
+                    .col(
+                        timestamp_with_time_zone(Outbox::NextAttemptAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_outbox_pending")
+                    .table(Outbox::Table)
+                    .col(Outbox::DeliveredAt)
+                    .col(Outbox::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_outbox_due")
+                    .table(Outbox::Table)
+                    .col(Outbox::DeliveredAt)
+                    .col(Outbox::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Outbox::Table).to_owned())
+            .await
+    }
+}
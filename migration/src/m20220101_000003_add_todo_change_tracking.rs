@@ -0,0 +1,180 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    ChangeSeq,
+}
+
+#[derive(DeriveIden)]
+enum TodoTombstone {
+    Table,
+    Id,
+    ChangeSeq,
+    DeletedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("CREATE SEQUENCE IF NOT EXISTS todo_change_seq")
+            .await?;
+
+        // Sequence values are handed out in write order but become visible in
+        // commit order. Writers hold a shared lock from drawing a value until
+        // they commit, so the watermark, taken under the exclusive lock, is a
+        // value below which every change is committed or rolled back. Writers
+        // never wait on each other, only briefly on a running watermark.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_next_change_seq() RETURNS bigint AS $$
+            BEGIN
+                PERFORM pg_advisory_xact_lock_shared(hashtext('todo_change_seq'));
+                RETURN nextval('todo_change_seq');
+            END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE OR REPLACE FUNCTION todo_change_watermark() RETURNS bigint AS $$
+            DECLARE
+                watermark bigint;
+            BEGIN
+                PERFORM pg_advisory_xact_lock(hashtext('todo_change_seq'));
+                SELECT CASE WHEN is_called THEN last_value ELSE last_value - 1 END
+                INTO watermark FROM todo_change_seq;
+                RETURN watermark;
+            END;
+            $$ LANGUAGE plpgsql;
+            "#,
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(
+                        big_integer(Todo::ChangeSeq)
+                            .not_null()
+                            .default(Expr::cust("todo_next_change_seq()")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_change_seq")
+                    .table(Todo::Table)
+                    .col(Todo::ChangeSeq)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoTombstone::Table)
+                    .if_not_exists()
+                    .col(uuid(TodoTombstone::Id).not_null().primary_key())
+                    .col(
+                        big_integer(TodoTombstone::ChangeSeq)
+                            .not_null()
+                            .default(Expr::cust("todo_next_change_seq()")),
+                    )
+                    .col(
+                        timestamp_with_time_zone(TodoTombstone::DeletedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_tombstone_change_seq")
+                    .table(TodoTombstone::Table)
+                    .col(TodoTombstone::ChangeSeq)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Triggers keep the change sequence correct for every write path,
+        // including bulk updates that bypass the repository.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_track_change() RETURNS trigger AS $$
+            BEGIN
+                IF TG_OP = 'UPDATE' THEN
+                    NEW.change_seq := todo_next_change_seq();
+                    RETURN NEW;
+                ELSIF TG_OP = 'INSERT' THEN
+                    DELETE FROM todo_tombstone WHERE id = NEW.id;
+                    RETURN NEW;
+                ELSE
+                    INSERT INTO todo_tombstone (id) VALUES (OLD.id)
+                    ON CONFLICT (id) DO UPDATE
+                    SET change_seq = todo_next_change_seq(), deleted_at = now();
+                    RETURN OLD;
+                END IF;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE TRIGGER todo_track_update BEFORE UPDATE ON todo
+                FOR EACH ROW EXECUTE FUNCTION todo_track_change();
+            CREATE TRIGGER todo_track_insert AFTER INSERT ON todo
+                FOR EACH ROW EXECUTE FUNCTION todo_track_change();
+            CREATE TRIGGER todo_track_delete AFTER DELETE ON todo
+                FOR EACH ROW EXECUTE FUNCTION todo_track_change();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+            DROP TRIGGER IF EXISTS todo_track_update ON todo;
+            DROP TRIGGER IF EXISTS todo_track_insert ON todo;
+            DROP TRIGGER IF EXISTS todo_track_delete ON todo;
+            DROP FUNCTION IF EXISTS todo_track_change();
+            "#,
+        )
+        .await?;
+
+        manager
+            .drop_table(Table::drop().table(TodoTombstone::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::ChangeSeq)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            DROP FUNCTION IF EXISTS todo_change_watermark();
+            DROP FUNCTION IF EXISTS todo_next_change_seq();
+            DROP SEQUENCE IF EXISTS todo_change_seq;
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
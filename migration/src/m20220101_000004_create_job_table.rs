@@ -0,0 +1,117 @@
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+    Name,
+    Payload,
+    Status,
+    Attempts,
+    MaxAttempts,
+    RunAt,
+    LockedAt,
+    LastError,
+    DedupeKey,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum JobStatus {
+    #[sea_orm(iden = "job_status")]
+    Enum,
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(JobStatus::Enum)
+                    .values([
+                        JobStatus::Pending,
+                        JobStatus::Running,
+                        JobStatus::Succeeded,
+                        JobStatus::Failed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Job::Table)
+                    .if_not_exists()
+                    .col(uuid(Job::Id).not_null().primary_key().default(Expr::cust("gen_random_uuid()")))
+                    .col(string(Job::Name).not_null())
+                    .col(json_binary(Job::Payload).not_null())
+                    .col(
+                        enumeration(
+                            Job::Status,
+                            JobStatus::Enum,
+                            [
+                                JobStatus::Pending,
+                                JobStatus::Running,
+                                JobStatus::Succeeded,
+                                JobStatus::Failed,
+                            ],
+                        )
+                        .not_null()
+                        .default("pending"),
+                    )
+                    .col(integer(Job::Attempts).not_null().default(0))
+                    .col(integer(Job::MaxAttempts).not_null())
+                    .col(
+                        timestamp_with_time_zone(Job::RunAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null(Job::LockedAt))
+                    .col(text_null(Job::LastError))
+                    .col(string_null(Job::DedupeKey).unique_key())
+                    .col(
+                        timestamp_with_time_zone(Job::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(Job::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_job_status_run_at")
+                    .table(Job::Table)
+                    .col(Job::Status)
+                    .col(Job::RunAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Job::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(JobStatus::Enum).to_owned())
+            .await
+    }
+}
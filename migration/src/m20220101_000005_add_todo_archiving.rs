@@ -0,0 +1,86 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    CompletedAt,
+    ArchivedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(timestamp_with_time_zone_null(Todo::CompletedAt))
+                    .add_column(timestamp_with_time_zone_null(Todo::ArchivedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared("UPDATE todo SET completed_at = updated_at WHERE completed")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_archived_at")
+                    .table(Todo::Table)
+                    .col(Todo::ArchivedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Stamp the completion time whenever a todo becomes completed, so the
+        // retention window is measured from completion rather than last edit.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_track_completion() RETURNS trigger AS $$
+            BEGIN
+                IF NOT NEW.completed THEN
+                    NEW.completed_at := NULL;
+                ELSIF TG_OP = 'INSERT' OR NOT OLD.completed THEN
+                    NEW.completed_at := now();
+                END IF;
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE TRIGGER todo_track_completion BEFORE INSERT OR UPDATE ON todo
+                FOR EACH ROW EXECUTE FUNCTION todo_track_completion();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+            DROP TRIGGER IF EXISTS todo_track_completion ON todo;
+            DROP FUNCTION IF EXISTS todo_track_completion();
+            "#,
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::CompletedAt)
+                    .drop_column(Todo::ArchivedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,140 @@
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Status,
+    Completed,
+}
+
+#[derive(DeriveIden)]
+enum TodoStatus {
+    #[sea_orm(iden = "todo_status")]
+    Enum,
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TodoStatus::Enum)
+                    .values([
+                        TodoStatus::Todo,
+                        TodoStatus::InProgress,
+                        TodoStatus::Blocked,
+                        TodoStatus::Done,
+                        TodoStatus::Cancelled,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(
+                        enumeration(
+                            Todo::Status,
+                            TodoStatus::Enum,
+                            [
+                                TodoStatus::Todo,
+                                TodoStatus::InProgress,
+                                TodoStatus::Blocked,
+                                TodoStatus::Done,
+                                TodoStatus::Cancelled,
+                            ],
+                        )
+                        .not_null()
+                        .default("todo"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Rewrite the completion trigger before touching rows so the backfill
+        // keeps the existing completed_at values.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_track_completion() RETURNS trigger AS $$
+            BEGIN
+                IF NEW.status <> 'done' THEN
+                    NEW.completed_at := NULL;
+                ELSIF TG_OP = 'INSERT' OR OLD.status <> 'done' THEN
+                    NEW.completed_at := COALESCE(NEW.completed_at, now());
+                END IF;
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            UPDATE todo SET status = 'done' WHERE completed;
+            "#,
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::Completed)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(boolean(Todo::Completed).not_null().default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_track_completion() RETURNS trigger AS $$
+            BEGIN
+                IF NOT NEW.completed THEN
+                    NEW.completed_at := NULL;
+                ELSIF TG_OP = 'INSERT' OR NOT OLD.completed THEN
+                    NEW.completed_at := COALESCE(NEW.completed_at, now());
+                END IF;
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            UPDATE todo SET completed = (status = 'done');
+            "#,
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(TodoStatus::Enum).to_owned())
+            .await
+    }
+}
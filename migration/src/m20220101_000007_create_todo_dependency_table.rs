@@ -0,0 +1,107 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    ProjectId,
+}
+
+#[derive(DeriveIden)]
+enum TodoDependency {
+    Table,
+    TodoId,
+    BlockedById,
+    CreatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(uuid_null(Todo::ProjectId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_project_id")
+                    .table(Todo::Table)
+                    .col(Todo::ProjectId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoDependency::Table)
+                    .if_not_exists()
+                    .col(uuid(TodoDependency::TodoId).not_null())
+                    .col(uuid(TodoDependency::BlockedById).not_null())
+                    .col(
+                        timestamp_with_time_zone(TodoDependency::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TodoDependency::TodoId)
+                            .col(TodoDependency::BlockedById),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_dependency_todo")
+                            .from(TodoDependency::Table, TodoDependency::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_dependency_blocked_by")
+                            .from(TodoDependency::Table, TodoDependency::BlockedById)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(
+                        Expr::col(TodoDependency::TodoId)
+                            .ne(Expr::col(TodoDependency::BlockedById)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_dependency_blocked_by_id")
+                    .table(TodoDependency::Table)
+                    .col(TodoDependency::BlockedById)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TodoDependency::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::ProjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
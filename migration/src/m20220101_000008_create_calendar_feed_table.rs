@@ -0,0 +1,170 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    OwnerId,
+    DueAt,
+}
+
+#[derive(DeriveIden)]
+enum TodoTombstone {
+    Table,
+    OwnerId,
+    ChangeSeq,
+}
+
+#[derive(DeriveIden)]
+enum CalendarFeed {
+    Table,
+    OwnerId,
+    Token,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(text_null(Todo::OwnerId))
+                    .add_column(timestamp_with_time_zone_null(Todo::DueAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_owner_id_due_at")
+                    .table(Todo::Table)
+                    .col(Todo::OwnerId)
+                    .col(Todo::DueAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tombstones keep the owner of the deleted todo so sync pulls can be
+        // limited to the caller's changes.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TodoTombstone::Table)
+                    .add_column(text_null(TodoTombstone::OwnerId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_tombstone_owner_id_change_seq")
+                    .table(TodoTombstone::Table)
+                    .col(TodoTombstone::OwnerId)
+                    .col(TodoTombstone::ChangeSeq)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION todo_track_change() RETURNS trigger AS $$
+                BEGIN
+                    IF TG_OP = 'UPDATE' THEN
+                        NEW.change_seq := todo_next_change_seq();
+                        RETURN NEW;
+                    ELSIF TG_OP = 'INSERT' THEN
+                        DELETE FROM todo_tombstone WHERE id = NEW.id;
+                        RETURN NEW;
+                    ELSE
+                        INSERT INTO todo_tombstone (id, owner_id) VALUES (OLD.id, OLD.owner_id)
+                        ON CONFLICT (id) DO UPDATE
+                        SET change_seq = todo_next_change_seq(), deleted_at = now(),
+                            owner_id = EXCLUDED.owner_id;
+                        RETURN OLD;
+                    END IF;
+                END;
+                $$ LANGUAGE plpgsql;
+                "#,
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CalendarFeed::Table)
+                    .if_not_exists()
+                    .col(text(CalendarFeed::OwnerId).primary_key())
+                    .col(text(CalendarFeed::Token).not_null().unique_key())
+                    .col(
+                        timestamp_with_time_zone(CalendarFeed::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(CalendarFeed::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CalendarFeed::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION todo_track_change() RETURNS trigger AS $$
+                BEGIN
+                    IF TG_OP = 'UPDATE' THEN
+                        NEW.change_seq := todo_next_change_seq();
+                        RETURN NEW;
+                    ELSIF TG_OP = 'INSERT' THEN
+                        DELETE FROM todo_tombstone WHERE id = NEW.id;
+                        RETURN NEW;
+                    ELSE
+                        INSERT INTO todo_tombstone (id) VALUES (OLD.id)
+                        ON CONFLICT (id) DO UPDATE
+                        SET change_seq = todo_next_change_seq(), deleted_at = now();
+                        RETURN OLD;
+                    END IF;
+                END;
+                $$ LANGUAGE plpgsql;
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TodoTombstone::Table)
+                    .drop_column(TodoTombstone::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::OwnerId)
+                    .drop_column(Todo::DueAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
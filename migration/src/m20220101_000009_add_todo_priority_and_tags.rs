@@ -0,0 +1,113 @@
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    Priority,
+}
+
+#[derive(DeriveIden)]
+enum TodoPriority {
+    #[sea_orm(iden = "todo_priority")]
+    Enum,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+#[derive(DeriveIden)]
+enum TodoTag {
+    Table,
+    TodoId,
+    Tag,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TodoPriority::Enum)
+                    .values([
+                        TodoPriority::Low,
+                        TodoPriority::Medium,
+                        TodoPriority::High,
+                        TodoPriority::Urgent,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(enumeration_null(
+                        Todo::Priority,
+                        TodoPriority::Enum,
+                        [
+                            TodoPriority::Low,
+                            TodoPriority::Medium,
+                            TodoPriority::High,
+                            TodoPriority::Urgent,
+                        ],
+                    ))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoTag::Table)
+                    .if_not_exists()
+                    .col(uuid(TodoTag::TodoId).not_null())
+                    .col(text(TodoTag::Tag).not_null())
+                    .primary_key(Index::create().col(TodoTag::TodoId).col(TodoTag::Tag))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_tag_todo")
+                            .from(TodoTag::Table, TodoTag::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_tag_tag")
+                    .table(TodoTag::Table)
+                    .col(TodoTag::Tag)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TodoTag::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::Priority)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(TodoPriority::Enum).to_owned())
+            .await
+    }
+}
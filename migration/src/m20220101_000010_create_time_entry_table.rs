@@ -0,0 +1,151 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    TrackedSeconds,
+}
+
+#[derive(DeriveIden)]
+enum TimeEntry {
+    Table,
+    Id,
+    TodoId,
+    UserId,
+    StartedAt,
+    EndedAt,
+    Note,
+    CreatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(big_integer(Todo::TrackedSeconds).not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimeEntry::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(TimeEntry::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(uuid(TimeEntry::TodoId).not_null())
+                    .col(text(TimeEntry::UserId).not_null())
+                    .col(timestamp_with_time_zone(TimeEntry::StartedAt).not_null())
+                    .col(timestamp_with_time_zone_null(TimeEntry::EndedAt))
+                    .col(text_null(TimeEntry::Note))
+                    .col(
+                        timestamp_with_time_zone(TimeEntry::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_time_entry_todo")
+                            .from(TimeEntry::Table, TimeEntry::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(
+                        Expr::col(TimeEntry::EndedAt)
+                            .is_null()
+                            .or(Expr::col(TimeEntry::EndedAt).gte(Expr::col(TimeEntry::StartedAt))),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_time_entry_user_id_started_at")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::UserId)
+                    .col(TimeEntry::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_time_entry_todo_id")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::TodoId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // At most one running timer per user, and finished entries are
+        // summed into todo.tracked_seconds so responses need no aggregate.
+        db.execute_unprepared(
+            r#"
+            CREATE UNIQUE INDEX idx_time_entry_running
+                ON time_entry (user_id) WHERE ended_at IS NULL;
+
+            CREATE OR REPLACE FUNCTION time_entry_track_total() RETURNS trigger AS $$
+            BEGIN
+                IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.ended_at IS NOT NULL THEN
+                    UPDATE todo
+                    SET tracked_seconds = tracked_seconds
+                        - EXTRACT(EPOCH FROM OLD.ended_at - OLD.started_at)::bigint
+                    WHERE id = OLD.todo_id;
+                END IF;
+                IF TG_OP IN ('UPDATE', 'INSERT') AND NEW.ended_at IS NOT NULL THEN
+                    UPDATE todo
+                    SET tracked_seconds = tracked_seconds
+                        + EXTRACT(EPOCH FROM NEW.ended_at - NEW.started_at)::bigint
+                    WHERE id = NEW.todo_id;
+                END IF;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE TRIGGER time_entry_track_total
+                AFTER INSERT OR UPDATE OR DELETE ON time_entry
+                FOR EACH ROW EXECUTE FUNCTION time_entry_track_total();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .drop_table(Table::drop().table(TimeEntry::Table).to_owned())
+            .await?;
+
+        db.execute_unprepared("DROP FUNCTION IF EXISTS time_entry_track_total()")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::TrackedSeconds)
+                    .to_owned(),
+            )
+            .await
+    }
+}
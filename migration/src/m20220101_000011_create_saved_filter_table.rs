@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum SavedFilter {
+    Table,
+    Id,
+    OwnerId,
+    Name,
+    Expression,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedFilter::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(SavedFilter::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(text(SavedFilter::OwnerId).not_null())
+                    .col(text(SavedFilter::Name).not_null())
+                    .col(json_binary(SavedFilter::Expression).not_null())
+                    .col(
+                        timestamp_with_time_zone(SavedFilter::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(SavedFilter::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_saved_filter_owner_id")
+                    .table(SavedFilter::Table)
+                    .col(SavedFilter::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedFilter::Table).to_owned())
+            .await
+    }
+}
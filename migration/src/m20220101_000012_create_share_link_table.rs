@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ShareLink {
+    Table,
+    Id,
+    Token,
+    OwnerId,
+    TodoId,
+    ProjectId,
+    ExpiresAt,
+    RevokedAt,
+    AccessCount,
+    LastAccessedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShareLink::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(ShareLink::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(text(ShareLink::Token).not_null().unique_key())
+                    .col(text(ShareLink::OwnerId).not_null())
+                    .col(uuid_null(ShareLink::TodoId))
+                    .col(uuid_null(ShareLink::ProjectId))
+                    .col(timestamp_with_time_zone_null(ShareLink::ExpiresAt))
+                    .col(timestamp_with_time_zone_null(ShareLink::RevokedAt))
+                    .col(big_integer(ShareLink::AccessCount).not_null().default(0))
+                    .col(timestamp_with_time_zone_null(ShareLink::LastAccessedAt))
+                    .col(
+                        timestamp_with_time_zone(ShareLink::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_link_todo")
+                            .from(ShareLink::Table, ShareLink::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // A link shares exactly one todo or one project list.
+                    .check(Expr::cust("(todo_id IS NULL) <> (project_id IS NULL)"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_link_owner_id")
+                    .table(ShareLink::Table)
+                    .col(ShareLink::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShareLink::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,209 @@
+use sea_orm_migration::{prelude::extension::postgres::Type, prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    AssigneeId,
+}
+
+#[derive(DeriveIden)]
+enum TodoComment {
+    Table,
+    Id,
+    TodoId,
+    AuthorId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum NotificationKind {
+    #[sea_orm(iden = "notification_kind")]
+    Enum,
+    Assigned,
+    Commented,
+    DuePassed,
+}
+
+#[derive(DeriveIden)]
+enum Notification {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    TodoId,
+    ActorId,
+    Message,
+    DedupeKey,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(text_null(Todo::AssigneeId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_assignee_id")
+                    .table(Todo::Table)
+                    .col(Todo::AssigneeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoComment::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(TodoComment::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(uuid(TodoComment::TodoId).not_null())
+                    .col(text(TodoComment::AuthorId).not_null())
+                    .col(text(TodoComment::Body).not_null())
+                    .col(
+                        timestamp_with_time_zone(TodoComment::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_comment_todo")
+                            .from(TodoComment::Table, TodoComment::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_comment_todo_id_created_at")
+                    .table(TodoComment::Table)
+                    .col(TodoComment::TodoId)
+                    .col(TodoComment::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(NotificationKind::Enum)
+                    .values([
+                        NotificationKind::Assigned,
+                        NotificationKind::Commented,
+                        NotificationKind::DuePassed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(Notification::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(text(Notification::UserId).not_null())
+                    .col(enumeration(
+                        Notification::Kind,
+                        NotificationKind::Enum,
+                        [
+                            NotificationKind::Assigned,
+                            NotificationKind::Commented,
+                            NotificationKind::DuePassed,
+                        ],
+                    ))
+                    .col(uuid(Notification::TodoId).not_null())
+                    .col(text_null(Notification::ActorId))
+                    .col(text(Notification::Message).not_null())
+                    // Set for notifications that must be created at most once.
+                    .col(text_null(Notification::DedupeKey).unique_key())
+                    .col(timestamp_with_time_zone_null(Notification::ReadAt))
+                    .col(
+                        timestamp_with_time_zone(Notification::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_todo")
+                            .from(Notification::Table, Notification::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_user_id_created_at")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .col(Notification::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Keeps unread counts cheap however large the inbox grows.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_notification_unread ON notification (user_id) WHERE read_at IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notification::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(NotificationKind::Enum).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TodoComment::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::AssigneeId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,137 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    RemindAt,
+    RemindedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserEmail {
+    Table,
+    UserId,
+    Email,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ReminderDelivery {
+    Table,
+    TodoId,
+    UserId,
+    RemindAt,
+    ClaimedUntil,
+    SentAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(timestamp_with_time_zone_null(Todo::RemindAt))
+                    .add_column(timestamp_with_time_zone_null(Todo::RemindedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The reminder sweep only ever looks at reminders not yet sent.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_todo_pending_reminder ON todo (remind_at) WHERE reminded_at IS NULL",
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserEmail::Table)
+                    .if_not_exists()
+                    .col(text(UserEmail::UserId).not_null().primary_key())
+                    .col(text(UserEmail::Email).not_null())
+                    .col(
+                        timestamp_with_time_zone(UserEmail::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(UserEmail::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per recipient of a reminder, so a failed send is retried for
+        // that recipient alone and nothing is held locked while mailing.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReminderDelivery::Table)
+                    .if_not_exists()
+                    .col(uuid(ReminderDelivery::TodoId).not_null())
+                    .col(text(ReminderDelivery::UserId).not_null())
+                    .col(timestamp_with_time_zone(ReminderDelivery::RemindAt).not_null())
+                    .col(
+                        timestamp_with_time_zone(ReminderDelivery::ClaimedUntil)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null(ReminderDelivery::SentAt))
+                    .primary_key(
+                        Index::create()
+                            .col(ReminderDelivery::TodoId)
+                            .col(ReminderDelivery::UserId)
+                            .col(ReminderDelivery::RemindAt),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_reminder_delivery_todo")
+                            .from(ReminderDelivery::Table, ReminderDelivery::TodoId)
+                            .to(Todo::Table, Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_reminder_delivery_pending ON reminder_delivery (claimed_until) WHERE sent_at IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReminderDelivery::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(UserEmail::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::RemindAt)
+                    .drop_column(Todo::RemindedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
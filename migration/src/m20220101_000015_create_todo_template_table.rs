@@ -0,0 +1,147 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Id,
+    ParentId,
+    Position,
+}
+
+#[derive(DeriveIden)]
+enum TodoTemplate {
+    Table,
+    Id,
+    OwnerId,
+    Title,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TodoTemplateItem {
+    Table,
+    TemplateId,
+    Position,
+    Title,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(uuid_null(Todo::ParentId))
+                    .add_column(integer_null(Todo::Position))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_todo_parent")
+                            .from_tbl(Todo::Table)
+                            .from_col(Todo::ParentId)
+                            .to_tbl(Todo::Table)
+                            .to_col(Todo::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_parent_id_position")
+                    .table(Todo::Table)
+                    .col(Todo::ParentId)
+                    .col(Todo::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoTemplate::Table)
+                    .if_not_exists()
+                    .col(
+                        uuid(TodoTemplate::Id)
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(text(TodoTemplate::OwnerId).not_null())
+                    .col(text(TodoTemplate::Title).not_null())
+                    .col(
+                        timestamp_with_time_zone(TodoTemplate::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone(TodoTemplate::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_template_owner_id")
+                    .table(TodoTemplate::Table)
+                    .col(TodoTemplate::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoTemplateItem::Table)
+                    .if_not_exists()
+                    .col(uuid(TodoTemplateItem::TemplateId).not_null())
+                    .col(integer(TodoTemplateItem::Position).not_null())
+                    .col(text(TodoTemplateItem::Title).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(TodoTemplateItem::TemplateId)
+                            .col(TodoTemplateItem::Position),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_template_item_template")
+                            .from(TodoTemplateItem::Table, TodoTemplateItem::TemplateId)
+                            .to(TodoTemplate::Table, TodoTemplate::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TodoTemplateItem::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TodoTemplate::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_foreign_key(Alias::new("fk_todo_parent"))
+                    .drop_column(Todo::ParentId)
+                    .drop_column(Todo::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+}
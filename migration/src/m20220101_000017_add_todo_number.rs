@@ -0,0 +1,133 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+enum Todo {
+    Table,
+    Number,
+}
+
+#[derive(DeriveIden)]
+enum TodoNumberCounter {
+    Table,
+    OwnerKey,
+    LastNumber,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // One row per owner. Todos without an owner are numbered from a
+        // sequence instead, so their inserts do not all wait on one row.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoNumberCounter::Table)
+                    .if_not_exists()
+                    .col(text(TodoNumberCounter::OwnerKey).not_null().primary_key())
+                    .col(integer(TodoNumberCounter::LastNumber).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .add_column(integer_null(Todo::Number))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            UPDATE todo SET number = numbered.number
+            FROM (
+                SELECT id, row_number() OVER (
+                    PARTITION BY owner_id ORDER BY created_at, id
+                ) AS number
+                FROM todo
+            ) numbered
+            WHERE todo.id = numbered.id;
+
+            INSERT INTO todo_number_counter (owner_key, last_number)
+            SELECT owner_id, max(number) FROM todo
+            WHERE owner_id IS NOT NULL
+            GROUP BY owner_id;
+
+            CREATE SEQUENCE todo_unowned_number_seq;
+
+            SELECT setval('todo_unowned_number_seq', max(number)) FROM todo
+            WHERE owner_id IS NULL
+            HAVING max(number) IS NOT NULL;
+
+            ALTER TABLE todo ALTER COLUMN number SET NOT NULL;
+
+            CREATE UNIQUE INDEX idx_todo_owner_number
+                ON todo (COALESCE(owner_id, ''), number);
+            "#,
+        )
+        .await?;
+
+        // The counter upsert locks the owner's row until the inserting
+        // transaction ends, so concurrent inserts get consecutive numbers and
+        // a rolled back insert leaves no gap. The sequence hands out numbers
+        // without locking; there a rolled back insert leaves a gap.
+        db.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION todo_assign_number() RETURNS trigger AS $$
+            BEGIN
+                IF NEW.owner_id IS NULL THEN
+                    NEW.number := nextval('todo_unowned_number_seq');
+                    RETURN NEW;
+                END IF;
+
+                INSERT INTO todo_number_counter (owner_key, last_number)
+                VALUES (NEW.owner_id, 1)
+                ON CONFLICT (owner_key) DO UPDATE
+                SET last_number = todo_number_counter.last_number + 1
+                RETURNING last_number INTO NEW.number;
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+
+            CREATE TRIGGER todo_assign_number BEFORE INSERT ON todo
+                FOR EACH ROW EXECUTE FUNCTION todo_assign_number();
+            "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+            DROP TRIGGER IF EXISTS todo_assign_number ON todo;
+            DROP FUNCTION IF EXISTS todo_assign_number();
+            DROP INDEX IF EXISTS idx_todo_owner_number;
+            DROP SEQUENCE IF EXISTS todo_unowned_number_seq;
+            "#,
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todo::Table)
+                    .drop_column(Todo::Number)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TodoNumberCounter::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,17 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // A vendored protoc keeps the build free of system dependencies.
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let well_known = protoc_bin_vendored::include_path()?;
+
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_protos(
+            &[PathBuf::from("proto/todo/v1/todo.proto")],
+            &[PathBuf::from("proto"), well_known],
+        )?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use tracing::error;
+
+use crate::{
+    common::error::{ApiError, ApiResult},
+    common::jwt::Claims,
+    common::negotiate::Negotiated,
+    common::state::AppState,
+    common::validated_path::ValidatedPath,
+    common::validated_query::ValidatedQuery,
+};
+
+use super::ics;
+use super::model::{CalendarTokenResponse, FeedQuery};
+use super::service::etag_matches;
+
+const FEED_CACHE_CONTROL: &str = "private, max-age=300";
+
+pub async fn get_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<CalendarTokenResponse>> {
+    let feed = state
+        .calendar_service
+        .get_token(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to get calendar token: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(CalendarTokenResponse::from(feed)))
+}
+
+pub async fn generate_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<CalendarTokenResponse>> {
+    let feed = state
+        .calendar_service
+        .generate_token(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to generate calendar token: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(CalendarTokenResponse::from(feed)))
+}
+
+pub async fn rotate_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<CalendarTokenResponse>> {
+    let feed = state
+        .calendar_service
+        .rotate_token(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to rotate calendar token: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(CalendarTokenResponse::from(feed)))
+}
+
+/// Serves `{token}.ics`. The version check is a single aggregate query, so
+/// clients revalidating an unchanged feed never load the todos.
+pub async fn feed(
+    State(state): State<AppState>,
+    ValidatedPath(file): ValidatedPath<String>,
+    ValidatedQuery(query): ValidatedQuery<FeedQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let token = file
+        .strip_suffix(".ics")
+        .ok_or(ApiError::NotFound("resource not found"))?;
+
+    let (owner_id, version) = state
+        .calendar_service
+        .feed_version(token)
+        .await
+        .map_err(|err| {
+            error!("Failed to resolve calendar feed: {:?}", err);
+            err
+        })?;
+
+    let etag = version.etag(query.component);
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, FEED_CACHE_CONTROL.to_string()),
+    ];
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let todos = state
+        .calendar_service
+        .feed_todos(&owner_id)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %owner_id, "Failed to load calendar feed: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics::render(&todos, query.component),
+    )
+        .into_response())
+}
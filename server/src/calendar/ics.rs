@@ -0,0 +1,205 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::sea_orm_active_enums::TodoStatus;
+use entity::todo::Model;
+use serde::Deserialize;
+
+const PRODID: &str = "-//axum-boilerplate//todos//EN";
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Calendar component used for each todo. Many calendar apps ignore
+/// `VTODO`, so clients can ask for events placed at the due date instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedComponent {
+    #[default]
+    Vtodo,
+    Vevent,
+}
+
+/// Renders todos as an RFC 5545 calendar. The output only depends on the
+/// todos themselves, so unchanged data yields byte-identical feeds.
+pub fn render(todos: &[Model], component: FeedComponent) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Todos".to_string(),
+    ];
+
+    for todo in todos {
+        match component {
+            FeedComponent::Vtodo => push_vtodo(&mut lines, todo),
+            FeedComponent::Vevent => push_vevent(&mut lines, todo),
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in lines {
+        fold_into(&mut out, &line);
+    }
+    out
+}
+
+fn push_vtodo(lines: &mut Vec<String>, todo: &Model) {
+    lines.push("BEGIN:VTODO".to_string());
+    push_common(lines, todo);
+    if let Some(due_at) = todo.due_at {
+        lines.push(format!("DUE:{}", timestamp(due_at)));
+    }
+    lines.push(format!("STATUS:{}", vtodo_status(todo.status)));
+    if let Some(completed_at) = todo.completed_at {
+        lines.push(format!("COMPLETED:{}", timestamp(completed_at)));
+    }
+    lines.push("END:VTODO".to_string());
+}
+
+fn push_vevent(lines: &mut Vec<String>, todo: &Model) {
+    let Some(due_at) = todo.due_at else {
+        return;
+    };
+
+    lines.push("BEGIN:VEVENT".to_string());
+    push_common(lines, todo);
+    lines.push(format!("DTSTART:{}", timestamp(due_at)));
+    lines.push(format!("DTEND:{}", timestamp(due_at)));
+    if todo.status == TodoStatus::Cancelled {
+        lines.push("STATUS:CANCELLED".to_string());
+    }
+    lines.push("END:VEVENT".to_string());
+}
+
+fn push_common(lines: &mut Vec<String>, todo: &Model) {
+    lines.push(format!("UID:{}", todo.id));
+    lines.push(format!("DTSTAMP:{}", timestamp(todo.updated_at)));
+    lines.push(format!("LAST-MODIFIED:{}", timestamp(todo.updated_at)));
+    lines.push(format!("SUMMARY:{}", escape_text(&todo.title)));
+}
+
+fn vtodo_status(status: TodoStatus) -> &'static str {
+    match status {
+        TodoStatus::Todo | TodoStatus::Blocked => "NEEDS-ACTION",
+        TodoStatus::InProgress => "IN-PROCESS",
+        TodoStatus::Done => "COMPLETED",
+        TodoStatus::Cancelled => "CANCELLED",
+    }
+}
+
+fn timestamp(value: DateTime<FixedOffset>) -> String {
+    value
+        .with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends `line` folded at 75 octets without splitting a UTF-8 sequence;
+/// continuation lines start with a single space.
+fn fold_into(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn todo(title: &str, status: TodoStatus, due: bool) -> Model {
+        let at = DateTime::parse_from_rfc3339("2024-03-01T09:30:00+02:00").unwrap();
+        Model {
+            id: Uuid::nil(),
+            title: title.to_string(),
+            created_at: at,
+            updated_at: at,
+            change_seq: 1,
+            completed_at: None,
+            archived_at: None,
+            status,
+            project_id: None,
+            owner_id: Some("user".to_string()),
+            due_at: due.then_some(at),
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: None,
+            remind_at: None,
+            reminded_at: None,
+            parent_id: None,
+            position: None,
+            description: None,
+            number: 1,
+        }
+    }
+
+    #[test]
+    fn test_render_vtodo() {
+        let feed = render(
+            &[todo("Pay rent", TodoStatus::InProgress, true)],
+            FeedComponent::Vtodo,
+        );
+
+        assert!(feed.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(feed.contains("BEGIN:VTODO\r\nUID:00000000-0000-0000-0000-000000000000\r\n"));
+        assert!(feed.contains("DUE:20240301T073000Z\r\n"));
+        assert!(feed.contains("STATUS:IN-PROCESS\r\n"));
+        assert!(feed.ends_with("END:VTODO\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_render_vevent_skips_todos_without_due_date() {
+        let feed = render(
+            &[
+                todo("With due date", TodoStatus::Todo, true),
+                todo("Without", TodoStatus::Todo, false),
+            ],
+            FeedComponent::Vevent,
+        );
+
+        assert_eq!(feed.matches("BEGIN:VEVENT").count(), 1);
+        assert!(feed.contains("DTSTART:20240301T073000Z\r\n"));
+        assert!(!feed.contains("Without"));
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a,b;c\\d\r\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn test_fold_long_lines_on_char_boundaries() {
+        let mut out = String::new();
+        fold_into(&mut out, &format!("SUMMARY:{}", "é".repeat(60)));
+
+        let lines: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "é".repeat(60))
+        );
+    }
+}
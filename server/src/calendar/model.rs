@@ -0,0 +1,35 @@
+use chrono::{DateTime, FixedOffset};
+use entity::calendar_feed::Model;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::common::serialization::timestamp;
+
+use super::ics::FeedComponent;
+
+#[derive(Serialize)]
+pub struct CalendarTokenResponse {
+    pub token: String,
+    pub feed_url: String,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub rotated_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for CalendarTokenResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            feed_url: format!("/api/v1/calendar/{}.ics", model.token),
+            token: model.token,
+            created_at: model.created_at,
+            rotated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct FeedQuery {
+    #[serde(default)]
+    pub component: FeedComponent,
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use entity::calendar_feed::{ActiveModel, Column, Entity, Model};
+use entity::todo::{Column as TodoColumn, Entity as TodoEntity, Model as TodoModel};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+use super::service::FeedVersion;
+
+#[async_trait]
+pub trait CalendarRepository: Send + Sync {
+    async fn get_by_owner(&self, owner_id: &str) -> Result<Option<Model>, DbErr>;
+    async fn get_by_token(&self, token: &str) -> Result<Model, DbErr>;
+    async fn save_token(&self, owner_id: &str, token: String) -> Result<Model, DbErr>;
+    async fn feed_version(&self, owner_id: &str) -> Result<FeedVersion, DbErr>;
+    async fn feed_todos(&self, owner_id: &str) -> Result<Vec<TodoModel>, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct CalendarRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl CalendarRepository for CalendarRepositoryImpl {
+    async fn get_by_owner(&self, owner_id: &str) -> Result<Option<Model>, DbErr> {
+        Entity::find_by_id(owner_id.to_string()).one(&self.db).await
+    }
+
+    async fn get_by_token(&self, token: &str) -> Result<Model, DbErr> {
+        Entity::find()
+            .filter(Column::Token.eq(token))
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Calendar feed not found".to_string()))
+    }
+
+    async fn save_token(&self, owner_id: &str, token: String) -> Result<Model, DbErr> {
+        Entity::insert(ActiveModel {
+            owner_id: Set(owner_id.to_string()),
+            token: Set(token),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::column(Column::OwnerId)
+                .update_column(Column::Token)
+                .value(Column::UpdatedAt, chrono::Utc::now())
+                .to_owned(),
+        )
+        .exec_with_returning(&self.db)
+        .await
+    }
+
+    async fn feed_version(&self, owner_id: &str) -> Result<FeedVersion, DbErr> {
+        let (count, last_change): (i64, Option<i64>) = TodoEntity::find()
+            .select_only()
+            .column_as(TodoColumn::Id.count(), "count")
+            .column_as(TodoColumn::ChangeSeq.max(), "last_change")
+            .filter(feed_condition(owner_id))
+            .into_tuple()
+            .one(&self.db)
+            .await?
+            .unwrap_or_default();
+
+        Ok(FeedVersion { count, last_change })
+    }
+
+    async fn feed_todos(&self, owner_id: &str) -> Result<Vec<TodoModel>, DbErr> {
+        TodoEntity::find()
+            .filter(feed_condition(owner_id))
+            .order_by_asc(TodoColumn::DueAt)
+            .order_by_asc(TodoColumn::Id)
+            .all(&self.db)
+            .await
+    }
+}
+
+/// Active todos of `owner_id` that have a due date.
+fn feed_condition(owner_id: &str) -> Condition {
+    Condition::all()
+        .add(TodoColumn::OwnerId.eq(owner_id))
+        .add(TodoColumn::DueAt.is_not_null())
+        .add(TodoColumn::ArchivedAt.is_null())
+}
@@ -0,0 +1,94 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    calendar::controller, common::negotiate::require_acceptable, common::state::AppState,
+    middleware::auth::is_authenticated,
+};
+
+pub fn init() -> Router<AppState> {
+    // Token management needs the caller's identity; the feed itself is
+    // authorized by the secret token in its URL.
+    Router::new()
+        .merge(token_routes().layer(middleware::from_fn(is_authenticated)))
+        .route("/{feed}", get(controller::feed))
+}
+
+/// The feed is iCalendar whatever `Accept` says; these answer in the
+/// negotiated format.
+fn token_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/token",
+            get(controller::get_token).post(controller::generate_token),
+        )
+        .route("/token/rotate", post(controller::rotate_token))
+        .layer(middleware::from_fn(require_acceptable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::jwt::Claims;
+    use crate::common::negotiate::negotiate;
+    use crate::common::serialization::JsonPolicy;
+    use crate::common::test_db;
+    use crate::config::Config;
+    use axum::{
+        body::Body,
+        extract::Request,
+        http::{header, StatusCode},
+        Extension,
+    };
+    use tower::ServiceExt;
+
+    fn config() -> Config {
+        let vars = [
+            ("DATABASE_URL", ""),
+            ("LOG_LEVEL", "info"),
+            ("JWKS_URI", ""),
+            ("JWT_AUDIENCE", ""),
+            ("JWT_ISSUER", ""),
+        ];
+        envy::from_iter(vars.map(|(name, value)| (name.to_string(), value.to_string()))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rotate_with_unacceptable_accept_keeps_the_token() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let state = AppState::new(db, &config());
+        let feed = state
+            .calendar_service
+            .generate_token("user-1")
+            .await
+            .unwrap();
+
+        let app = token_routes()
+            .layer(Extension(Claims {
+                sub: "user-1".to_string(),
+                exp: 0,
+            }))
+            .layer(middleware::from_fn_with_state(
+                JsonPolicy::default(),
+                negotiate,
+            ))
+            .with_state(state.clone());
+        let request = Request::builder()
+            .method("POST")
+            .uri("/token/rotate")
+            .header(header::ACCEPT, "application/xml")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        let current = state.calendar_service.get_token("user-1").await.unwrap();
+        assert_eq!(current.token, feed.token);
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use entity::calendar_feed::Model;
+use entity::todo::Model as TodoModel;
+
+use crate::common::error::{ServiceError, ServiceResult};
+use crate::common::token;
+
+use super::ics::FeedComponent;
+use super::repository::CalendarRepository;
+
+#[derive(Clone)]
+pub struct CalendarService<R: CalendarRepository> {
+    pub repo: Arc<R>,
+}
+
+/// Cheap fingerprint of a feed's todos. Every write bumps a todo's change
+/// sequence and deletions lower the count, so any change alters it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeedVersion {
+    pub count: i64,
+    pub last_change: Option<i64>,
+}
+
+impl FeedVersion {
+    pub fn etag(&self, component: FeedComponent) -> String {
+        let component = match component {
+            FeedComponent::Vtodo => "vtodo",
+            FeedComponent::Vevent => "vevent",
+        };
+        format!(
+            "\"{}-{}-{}\"",
+            component,
+            self.count,
+            self.last_change.unwrap_or(0)
+        )
+    }
+}
+
+impl<R: CalendarRepository> CalendarService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        CalendarService { repo }
+    }
+
+    pub async fn get_token(&self, owner_id: &str) -> ServiceResult<Model> {
+        self.repo
+            .get_by_owner(owner_id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound)
+    }
+
+    /// Returns the owner's feed token, creating one on first use.
+    pub async fn generate_token(&self, owner_id: &str) -> ServiceResult<Model> {
+        match self
+            .repo
+            .get_by_owner(owner_id)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            Some(feed) => Ok(feed),
+            None => self.rotate_token(owner_id).await,
+        }
+    }
+
+    /// Replaces the owner's token; the previous feed URL stops working.
+    pub async fn rotate_token(&self, owner_id: &str) -> ServiceResult<Model> {
+        self.repo
+            .save_token(owner_id, token::generate())
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Resolves a feed token to its owner and the current feed version.
+    pub async fn feed_version(&self, token: &str) -> ServiceResult<(String, FeedVersion)> {
+        let feed = self
+            .repo
+            .get_by_token(token)
+            .await
+            .map_err(ServiceError::from)?;
+        let version = self
+            .repo
+            .feed_version(&feed.owner_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok((feed.owner_id, version))
+    }
+
+    pub async fn feed_todos(&self, owner_id: &str) -> ServiceResult<Vec<TodoModel>> {
+        self.repo
+            .feed_todos(owner_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+/// Whether an `If-None-Match` header value matches `etag`, using the weak
+/// comparison RFC 9110 prescribes for that header.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_changes_with_version_and_component() {
+        let version = FeedVersion {
+            count: 3,
+            last_change: Some(42),
+        };
+        let newer = FeedVersion {
+            last_change: Some(43),
+            ..version
+        };
+
+        assert_eq!(version.etag(FeedComponent::Vtodo), "\"vtodo-3-42\"");
+        assert_ne!(
+            version.etag(FeedComponent::Vtodo),
+            newer.etag(FeedComponent::Vtodo)
+        );
+        assert_ne!(
+            version.etag(FeedComponent::Vtodo),
+            version.etag(FeedComponent::Vevent)
+        );
+        assert_eq!(
+            FeedVersion::default().etag(FeedComponent::Vtodo),
+            "\"vtodo-0-0\""
+        );
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = "\"vtodo-3-42\"";
+
+        assert!(etag_matches("\"vtodo-3-42\"", etag));
+        assert!(etag_matches("W/\"vtodo-3-42\"", etag));
+        assert!(etag_matches("\"other\", \"vtodo-3-42\"", etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches("\"vtodo-3-41\"", etag));
+        assert!(!etag_matches("", etag));
+    }
+}
@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+/// A `{data, meta, links}` body, so metadata can grow without breaking clients.
+#[derive(Debug, Serialize)]
+pub struct Envelope<T> {
+    pub data: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ListMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<PageLinks>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListMeta {
+    /// Items in `data`.
+    pub count: usize,
+    /// Items across all pages; left out when counting was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    pub self_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            meta: None,
+            links: None,
+        }
+    }
+}
+
+impl<T> Envelope<Vec<T>> {
+    pub fn list(data: Vec<T>) -> Self {
+        let meta = ListMeta {
+            count: data.len(),
+            ..ListMeta::default()
+        };
+        Self {
+            data,
+            meta: Some(meta),
+            links: None,
+        }
+    }
+}
+
+impl PageLinks {
+    /// The links as an RFC 8288 `Link` header value.
+    pub fn header(&self) -> String {
+        [
+            Some((&self.self_, "self")),
+            self.next.as_ref().map(|next| (next, "next")),
+            self.prev.as_ref().map(|prev| (prev, "prev")),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(href, rel)| format!("<{href}>; rel=\"{rel}\""))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_header_lists_present_relations() {
+        let links = PageLinks {
+            self_: "/todos?limit=10&offset=10".to_string(),
+            next: None,
+            prev: Some("/todos?limit=10&offset=0".to_string()),
+        };
+
+        assert_eq!(
+            links.header(),
+            "</todos?limit=10&offset=10>; rel=\"self\", </todos?limit=10&offset=0>; rel=\"prev\""
+        );
+    }
+}
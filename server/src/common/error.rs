@@ -2,7 +2,7 @@ pub mod api;
 pub mod database;
 pub mod service;
 
-pub use api::{ApiError, ApiResult};
+pub use api::{ApiError, ApiResult, ErrorResponse};
 pub use database::DatabaseError;
 pub use service::{ServiceError, ServiceResult};
 
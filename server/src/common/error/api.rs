@@ -3,13 +3,14 @@ use std::borrow::Cow;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
 };
-use serde_json::json;
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
 use validator::ValidationErrors;
 
 use super::{database::DatabaseError, service::ServiceError};
+use crate::common::negotiate::Negotiated;
 
 pub type ApiResult<T> = Result<T, ApiError>;
 
@@ -25,10 +26,27 @@ pub enum ApiError {
     BadRequest(String),
     #[error("conflict")]
     Conflict(&'static str),
+    #[error("not acceptable")]
+    NotAcceptable(&'static str),
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 }
 
+/// JSON body of every error response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    /// Same as the HTTP status code.
+    #[schema(example = 404)]
+    pub status: u16,
+    #[schema(example = "resource not found")]
+    pub message: String,
+}
+
 impl From<ValidationErrors> for ApiError {
     fn from(err: ValidationErrors) -> Self {
         ApiError::BadRequest(err.to_string())
@@ -41,6 +59,7 @@ impl From<ServiceError> for ApiError {
             ServiceError::NotFound => ApiError::NotFound("resource not found"),
             ServiceError::AlreadyExists => ApiError::Conflict("resource already exists"),
             ServiceError::ValidationError => ApiError::BadRequest("validation failed".into()),
+            ServiceError::Conflict(msg) => ApiError::Conflict(msg),
             ServiceError::DatabaseError(db) => match db {
                 DatabaseError::NotFound => ApiError::NotFound("resource not found"),
                 DatabaseError::Conflict => ApiError::Conflict("resource already exists"),
@@ -62,33 +81,35 @@ impl ApiError {
             NotFound(_) => StatusCode::NOT_FOUND,
             BadRequest(_) => StatusCode::BAD_REQUEST,
             Conflict(_) => StatusCode::CONFLICT,
+            NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
             Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
-    fn client_msg(&self) -> Cow<'static, str> {
+    pub fn client_msg(&self) -> Cow<'static, str> {
         use ApiError::*;
         match self {
-            Unauthorized(m) | Forbidden(m) | NotFound(m) | Conflict(m) => Cow::Borrowed(m),
+            Unauthorized(m) | Forbidden(m) | NotFound(m) | Conflict(m) | NotAcceptable(m) => {
+                Cow::Borrowed(m)
+            }
             BadRequest(m) => Cow::Owned(m.clone()),
             Internal(_) => Cow::Borrowed("an internal error occurred"),
         }
     }
+
+    pub fn body(&self) -> ErrorResponse {
+        ErrorResponse {
+            error: ErrorDetail {
+                status: self.status().as_u16(),
+                message: self.client_msg().into_owned(),
+            },
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let status = self.status();
-        (
-            status,
-            Json(json!({
-                "error": {
-                    "status": status.as_u16(),
-                    "message": self.client_msg(),
-                }
-            })),
-        )
-            .into_response()
+        (self.status(), Negotiated(self.body())).into_response()
     }
 }
 
@@ -38,7 +38,18 @@ impl From<DbErr> for DatabaseError {
     }
 }
 
-fn is_unique_constraint_violation(err: &DbErr) -> bool {
+/// A serializable transaction was aborted and can be retried.
+pub fn is_serialization_failure(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(db_err)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(db_err))) => {
+            db_err.code().as_deref() == Some("40001")
+        }
+        _ => false,
+    }
+}
+
+pub fn is_unique_constraint_violation(err: &DbErr) -> bool {
     matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
 }
 
@@ -13,6 +13,8 @@ pub enum ServiceError {
     AlreadyExists,
     #[error("validation error")]
     ValidationError,
+    #[error("conflict: {0}")]
+    Conflict(&'static str),
     #[error("database error")]
     DatabaseError(#[from] DatabaseError),
 }
@@ -0,0 +1,84 @@
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use serde::Serialize;
+use tokio_stream::{Stream, StreamExt};
+
+use super::serialization::JsonPolicy;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// A chunked `application/x-ndjson` body with one JSON document per record.
+/// Records are pulled only as fast as the client reads, and an error ends the
+/// body early since the status line has already been sent.
+pub struct NdJson<S>(pub S);
+
+impl<S, T, E> IntoResponse for NdJson<S>
+where
+    S: Stream<Item = Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    fn into_response(self) -> Response {
+        // The body is polled after the request scope ends, so the policy is
+        // captured here.
+        let policy = JsonPolicy::current();
+        let lines = self.0.map(move |record| {
+            record
+                .map_err(Into::into)
+                .and_then(|record| line(policy, &record))
+        });
+
+        (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(NDJSON_CONTENT_TYPE),
+            )],
+            Body::from_stream(lines),
+        )
+            .into_response()
+    }
+}
+
+fn line<T: Serialize>(policy: JsonPolicy, record: &T) -> Result<Bytes, BoxError> {
+    let mut line = policy.to_vec(record)?;
+    line.push(b'\n');
+    Ok(Bytes::from(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn writes_one_line_per_record() {
+        let records = tokio_stream::iter(vec![
+            Ok::<_, BoxError>(json!({"id": 1})),
+            Ok(json!({"id": 2})),
+        ]);
+        let response = NdJson(records).into_response();
+
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            NDJSON_CONTENT_TYPE
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"{\"id\":1}\n{\"id\":2}\n");
+    }
+
+    #[tokio::test]
+    async fn error_aborts_the_body() {
+        let records = tokio_stream::iter(vec![
+            Ok(json!({"id": 1})),
+            Err(BoxError::from("connection reset")),
+        ]);
+        let response = NdJson(records).into_response();
+
+        assert!(to_bytes(response.into_body(), usize::MAX).await.is_err());
+    }
+}
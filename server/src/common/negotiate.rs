@@ -0,0 +1,324 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::error;
+
+use super::error::ApiError;
+use super::serialization::JsonPolicy;
+
+pub const NOT_ACCEPTABLE: &str =
+    "Accept must allow application/json, application/msgpack or application/cbor";
+
+/// A body format both accepted in requests and produced in responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+tokio::task_local! {
+    /// Response format chosen by [`negotiate`] for the current request.
+    /// `None` when `Accept` lists nothing we can produce.
+    static RESPONSE_FORMAT: Option<Format>;
+}
+
+impl Format {
+    /// Also understands structured syntax suffixes, so
+    /// `application/problem+json` is JSON and `application/foo+cbor` is CBOR.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next()?.trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "application/json" => Some(Format::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Format::MessagePack)
+            }
+            "application/cbor" => Some(Format::Cbor),
+            other => match other.strip_prefix("application/")?.rsplit_once('+')? {
+                ("", _) => None,
+                (_, "json") => Some(Format::Json),
+                (_, "cbor") => Some(Format::Cbor),
+                _ => None,
+            },
+        }
+    }
+
+    /// The request body format, `None` without a supported `Content-Type`.
+    pub fn from_content_type(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Format::from_media_type)
+    }
+
+    /// The supported format with the highest `q` in `Accept`: JSON without
+    /// the header, `None` when it lists nothing supported.
+    pub fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let mut accept = headers.get_all(header::ACCEPT).iter().peekable();
+        if accept.peek().is_none() {
+            return Some(Format::Json);
+        }
+
+        let mut best: Option<(Format, f32)> = None;
+        for value in accept {
+            let Ok(value) = value.to_str() else { continue };
+            for range in value.split(',') {
+                let mut params = range.split(';');
+                let media_type = params.next().unwrap_or_default().trim();
+                let quality = params
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let format = match media_type {
+                    "*/*" | "application/*" => Some(Format::Json),
+                    other => Format::from_media_type(other),
+                };
+                if let Some(format) = format {
+                    if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                        best = Some((format, quality));
+                    }
+                }
+            }
+        }
+        best.map(|(format, _)| format)
+    }
+
+    /// The format of the current response, JSON outside [`negotiate`] and
+    /// `None` when the client accepts none of ours.
+    pub fn current() -> Option<Self> {
+        RESPONSE_FORMAT
+            .try_with(|format| *format)
+            .unwrap_or(Some(Format::Json))
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::MessagePack => "application/msgpack",
+            Format::Cbor => "application/cbor",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::MessagePack => "MessagePack",
+            Format::Cbor => "CBOR",
+        }
+    }
+
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Format::Json => JsonPolicy::current()
+                .to_vec(value)
+                .map_err(|err| err.to_string()),
+            Format::MessagePack => rmp_serde::to_vec_named(value).map_err(|err| err.to_string()),
+            Format::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(value, &mut buffer).map_err(|err| err.to_string())?;
+                Ok(buffer)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Format::Json => serde_json::from_slice(bytes).map_err(|err| err.to_string()),
+            Format::MessagePack => rmp_serde::from_slice(bytes).map_err(|err| err.to_string()),
+            Format::Cbor => ciborium::from_reader(bytes).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Picks the response format from `Accept` and installs the JSON policy for
+/// everything the request runs. It refuses nothing itself: routes with their
+/// own media type (calendar feeds, NDJSON exports, the docs pages) ignore the
+/// choice, and [`require_acceptable`] refuses the others up front.
+pub async fn negotiate(State(policy): State<JsonPolicy>, req: Request, next: Next) -> Response {
+    let format = Format::from_accept(req.headers());
+    let mut response = RESPONSE_FORMAT
+        .scope(format, policy.scope(next.run(req)))
+        .await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    response
+}
+
+/// Answers `406 Not Acceptable` before the handler runs when `Accept` lists
+/// none of our formats, so a write is never made and then not reported.
+/// Layered onto the routes that answer with [`Negotiated`] bodies.
+pub async fn require_acceptable(req: Request, next: Next) -> Result<Response, ApiError> {
+    if Format::from_accept(req.headers()).is_none() {
+        return Err(ApiError::NotAcceptable(NOT_ACCEPTABLE));
+    }
+    Ok(next.run(req).await)
+}
+
+/// A response body encoded in the negotiated [`Format`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Negotiated<T>(pub T);
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        let Some(format) = Format::current() else {
+            // Nothing listed can be produced; say so in JSON rather than
+            // send an empty 406.
+            let err = ApiError::NotAcceptable(NOT_ACCEPTABLE);
+            return (err.status(), Json(err.body())).into_response();
+        };
+        match format.encode(&self.0) {
+            Ok(body) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(format.content_type()),
+                )],
+                body,
+            )
+                .into_response(),
+            Err(err) => {
+                error!("Failed to encode {} response: {}", format.name(), err);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    fn accept(value: &'static str) -> Option<Format> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+        Format::from_accept(&headers)
+    }
+
+    #[test]
+    fn test_accept_picks_the_preferred_supported_format() {
+        assert_eq!(accept("application/cbor"), Some(Format::Cbor));
+        assert_eq!(
+            accept("application/json;q=0.5, application/msgpack"),
+            Some(Format::MessagePack)
+        );
+        assert_eq!(accept("text/html, */*;q=0.1"), Some(Format::Json));
+        assert_eq!(accept("application/problem+json"), Some(Format::Json));
+        assert_eq!(
+            accept("application/vnd.api+cbor, application/json;q=0.5"),
+            Some(Format::Cbor)
+        );
+        assert_eq!(Format::from_accept(&HeaderMap::new()), Some(Format::Json));
+    }
+
+    #[test]
+    fn test_accept_without_a_supported_format_is_refused() {
+        assert_eq!(accept("application/xml"), None);
+        assert_eq!(accept("text/html, application/cbor;q=0"), None);
+        assert_eq!(accept("application/+json"), None);
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Body {
+            title: String,
+            tags: Vec<String>,
+        }
+        let body = Body {
+            title: "Ship".to_string(),
+            tags: vec!["ops".to_string()],
+        };
+
+        for format in [Format::Json, Format::MessagePack, Format::Cbor] {
+            let bytes = format.encode(&body).unwrap();
+            assert_eq!(format.decode::<Body>(&bytes).unwrap(), body, "{format:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_negotiated_uses_the_scoped_format() {
+        let response = RESPONSE_FORMAT
+            .scope(Some(Format::Cbor), async {
+                Negotiated("ok").into_response()
+            })
+            .await;
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/cbor");
+    }
+
+    #[tokio::test]
+    async fn test_error_bodies_follow_accept() {
+        use crate::common::error::ApiError;
+        use axum::{body::Body, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async { Err::<(), _>(ApiError::NotFound("resource not found")) }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                JsonPolicy::default(),
+                negotiate,
+            ));
+        let request = Request::builder()
+            .uri("/")
+            .header(header::ACCEPT, "application/msgpack")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/msgpack"
+        );
+        assert_eq!(response.headers()[header::VARY], "accept");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_accept_gets_not_acceptable() {
+        use crate::common::validated_body::ValidatedBody;
+        use axum::{body::Body, routing::post, Router};
+        use tower::ServiceExt;
+
+        #[derive(Deserialize, validator::Validate)]
+        struct Payload {}
+
+        let app = Router::new()
+            .route(
+                "/",
+                // A write without a body in reply is still refused up front.
+                post(|_: ValidatedBody<Payload>| async { StatusCode::CREATED })
+                    .get(|| async { Negotiated("ok") }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                JsonPolicy::default(),
+                negotiate,
+            ));
+        let request = |method: &str| {
+            Request::builder()
+                .method(method)
+                .uri("/")
+                .header(header::ACCEPT, "application/xml")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap()
+        };
+
+        for method in ["GET", "POST"] {
+            let response = app.clone().oneshot(request(method)).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE, "{method}");
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        }
+    }
+}
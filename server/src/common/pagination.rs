@@ -0,0 +1,296 @@
+use std::future::Future;
+
+use axum::{
+    extract::{FromRequestParts, OriginalUri},
+    http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+use super::envelope::{Envelope, ListMeta, PageLinks};
+use super::error::ApiError;
+use super::negotiate::Negotiated;
+use super::validated_query::ValidatedQuery;
+
+fn default_count() -> bool {
+    true
+}
+
+/// Paging parameters accepted by list routes.
+#[derive(Debug, Clone, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page size; everything after `offset` is returned when omitted.
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    pub offset: u64,
+    /// Returns `{data, meta, links}` instead of a bare array with `Link` and
+    /// `X-Total-Count` headers.
+    #[serde(default)]
+    pub envelope: bool,
+    /// Set to `false` to skip counting the total, which can be expensive.
+    #[serde(default = "default_count")]
+    pub count: bool,
+}
+
+/// [`PageQuery`] together with the URI the page links are built from.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    pub query: PageQuery,
+    uri: Uri,
+}
+
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ValidatedQuery(query) = ValidatedQuery::from_request_parts(parts, state).await?;
+        // Nested routers see a stripped path; links need the one the client used.
+        let uri = match parts.extensions.get::<OriginalUri>() {
+            Some(OriginalUri(uri)) => uri.clone(),
+            None => parts.uri.clone(),
+        };
+
+        Ok(Self { query, uri })
+    }
+}
+
+impl Pagination {
+    /// Rows to fetch: one past the page, so `next` is only linked when another
+    /// page exists.
+    pub fn fetch_limit(&self) -> Option<u64> {
+        self.query.limit.map(|limit| limit + 1)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.query.offset
+    }
+
+    /// The number of items across all pages. It is derived from `rows` when
+    /// they reach the end of the list, otherwise `count` is run unless the
+    /// client turned counting off.
+    pub async fn total<T, F, Fut, E>(&self, rows: &[T], count: F) -> Result<Option<u64>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64, E>>,
+    {
+        let PageQuery { limit, offset, .. } = self.query;
+        let rows_len = rows.len() as u64;
+        let reached_end = limit.is_none_or(|limit| rows_len <= limit);
+
+        if reached_end && (offset == 0 || rows_len > 0) {
+            Ok(Some(offset + rows_len))
+        } else if self.query.count {
+            count().await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Builds the page from rows fetched with [`Self::fetch_limit`].
+    pub fn page<T>(&self, mut rows: Vec<T>, total: Option<u64>) -> Page<T> {
+        let PageQuery {
+            limit,
+            offset,
+            envelope,
+            ..
+        } = self.query;
+        let has_next = limit.is_some_and(|limit| rows.len() as u64 > limit);
+        if let Some(limit) = limit {
+            rows.truncate(limit as usize);
+        }
+
+        let links = PageLinks {
+            self_: self.href(limit, offset),
+            next: limit
+                .filter(|_| has_next)
+                .map(|limit| self.href(Some(limit), offset + limit)),
+            prev: (offset > 0)
+                .then(|| self.href(limit, offset.saturating_sub(limit.unwrap_or(offset)))),
+        };
+        let meta = ListMeta {
+            count: rows.len(),
+            total,
+            limit,
+            offset: Some(offset),
+        };
+
+        Page {
+            data: rows,
+            meta,
+            links,
+            envelope,
+        }
+    }
+
+    /// The request URI with `limit` and `offset` replaced.
+    fn href(&self, limit: Option<u64>, offset: u64) -> String {
+        let mut params: Vec<String> = self
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !pair.is_empty() && key != "limit" && key != "offset"
+            })
+            .map(String::from)
+            .collect();
+        if let Some(limit) = limit {
+            params.push(format!("limit={limit}"));
+        }
+        if offset > 0 {
+            params.push(format!("offset={offset}"));
+        }
+
+        match params.is_empty() {
+            true => self.uri.path().to_string(),
+            false => format!("{}?{}", self.uri.path(), params.join("&")),
+        }
+    }
+}
+
+/// One page of a list, with its links in an RFC 8288 `Link` header. The body
+/// is an [`Envelope`] for `?envelope=true`, otherwise a bare array with the
+/// total in `X-Total-Count`.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub meta: ListMeta,
+    pub links: PageLinks,
+    envelope: bool,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            data: self.data.into_iter().map(f).collect(),
+            meta: self.meta,
+            links: self.links,
+            envelope: self.envelope,
+        }
+    }
+
+    /// Envelopes the page whatever the request asked for.
+    pub fn enveloped(self) -> Self {
+        Page {
+            envelope: true,
+            ..self
+        }
+    }
+
+    pub fn into_envelope(self) -> Envelope<Vec<T>> {
+        Envelope {
+            data: self.data,
+            meta: Some(self.meta),
+            links: Some(self.links),
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let mut headers = HeaderMap::new();
+        if let Ok(link) = HeaderValue::from_str(&self.links.header()) {
+            headers.insert(header::LINK, link);
+        }
+        if self.envelope {
+            return (headers, Negotiated(self.into_envelope())).into_response();
+        }
+
+        if let Some(total) = self.meta.total {
+            headers.insert(HeaderName::from_static("x-total-count"), total.into());
+        }
+
+        (headers, Negotiated(self.data)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pagination(uri: &str, limit: Option<u64>, offset: u64) -> Pagination {
+        Pagination {
+            query: PageQuery {
+                limit,
+                offset,
+                envelope: false,
+                count: true,
+            },
+            uri: uri.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn middle_page_links_both_ways_and_keeps_other_params() {
+        let pagination = pagination("/api/v1/todos?status=todo&limit=2&offset=2", Some(2), 2);
+        let page = pagination.page(vec![1, 2, 3], None);
+
+        assert_eq!(page.data, vec![1, 2]);
+        assert_eq!(
+            page.links.self_,
+            "/api/v1/todos?status=todo&limit=2&offset=2"
+        );
+        assert_eq!(
+            page.links.next.as_deref(),
+            Some("/api/v1/todos?status=todo&limit=2&offset=4")
+        );
+        assert_eq!(
+            page.links.prev.as_deref(),
+            Some("/api/v1/todos?status=todo&limit=2")
+        );
+    }
+
+    #[test]
+    fn last_page_has_no_next_link() {
+        let page = pagination("/api/v1/todos?limit=2", Some(2), 0).page(vec![1, 2], None);
+
+        assert!(page.links.next.is_none());
+        assert!(page.links.prev.is_none());
+    }
+
+    #[tokio::test]
+    async fn total_is_counted_only_when_more_rows_follow() {
+        let count = || async { Ok::<_, ()>(42) };
+
+        let last = pagination("/todos", Some(2), 4);
+        assert_eq!(last.total(&[1], count).await, Ok(Some(5)));
+
+        let first = pagination("/todos", Some(2), 0);
+        assert_eq!(first.total(&[1, 2, 3], count).await, Ok(Some(42)));
+
+        let mut uncounted = pagination("/todos", Some(2), 0);
+        uncounted.query.count = false;
+        assert_eq!(uncounted.total(&[1, 2, 3], count).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn envelope_is_opt_in_and_links_are_always_sent() {
+        let body = |response: Response| async {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        let bare = pagination("/todos?limit=1", Some(1), 0)
+            .page(vec![1, 2], Some(2))
+            .into_response();
+        assert!(bare.headers().contains_key(header::LINK));
+        assert_eq!(bare.headers()["x-total-count"], "2");
+        assert_eq!(body(bare).await, serde_json::json!([1]));
+
+        let mut enveloped = pagination("/todos?limit=1", Some(1), 0);
+        enveloped.query.envelope = true;
+        let enveloped = enveloped.page(vec![1, 2], Some(2)).into_response();
+        assert!(enveloped.headers().contains_key(header::LINK));
+        assert_eq!(body(enveloped).await["meta"]["total"], 2);
+    }
+}
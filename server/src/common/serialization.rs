@@ -0,0 +1,230 @@
+use std::fmt::Display;
+use std::future::Future;
+
+use chrono::{DateTime, SecondsFormat, TimeZone};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::config::Config;
+
+mod de;
+mod ser;
+
+pub use de::AnyCase;
+pub use ser::CamelCaseFields;
+
+/// How JSON response fields are named, from `JSON_FIELD_NAMING`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum FieldNaming {
+    #[default]
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+}
+
+/// Fractional seconds of JSON response timestamps, from
+/// `JSON_TIMESTAMP_PRECISION`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampPrecision {
+    /// As many digits as needed, the default RFC 3339 output.
+    #[default]
+    Auto,
+    /// Always three digits, with `Z` for UTC.
+    Millis,
+}
+
+tokio::task_local! {
+    /// Policy installed by the `negotiate` middleware for the current request.
+    static JSON_POLICY: JsonPolicy;
+    /// Precision of the document [`JsonPolicy::to_vec`] is writing.
+    static WRITING_TIMESTAMPS: TimestampPrecision;
+}
+
+/// How response models are written as JSON. Names are applied by
+/// [`CamelCaseFields`] and timestamps by [`timestamp`] while serializing, so
+/// one set of structs serves every convention.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonPolicy {
+    pub naming: FieldNaming,
+    pub timestamps: TimestampPrecision,
+}
+
+impl JsonPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            naming: config.json_field_naming,
+            timestamps: config.json_timestamp_precision,
+        }
+    }
+
+    /// The policy of the current request, the default outside `negotiate`.
+    pub fn current() -> Self {
+        JSON_POLICY.try_with(|policy| *policy).unwrap_or_default()
+    }
+
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        JSON_POLICY.scope(self, f).await
+    }
+
+    pub fn to_vec<T: Serialize + ?Sized>(self, value: &T) -> serde_json::Result<Vec<u8>> {
+        WRITING_TIMESTAMPS.sync_scope(self.timestamps, || match self.naming {
+            FieldNaming::SnakeCase => serde_json::to_vec(value),
+            FieldNaming::CamelCase => serde_json::to_vec(&CamelCaseFields(value)),
+        })
+    }
+}
+
+/// A response timestamp, or an optional one, written with the precision of
+/// the JSON document being written. Other formats keep chrono's output.
+pub struct Timestamp<'a, T>(pub &'a T);
+
+impl<Tz: TimeZone> Serialize for Timestamp<'_, DateTime<Tz>>
+where
+    Tz::Offset: Display,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match WRITING_TIMESTAMPS.try_with(|precision| *precision) {
+            Ok(TimestampPrecision::Millis) => {
+                serializer.serialize_str(&self.0.to_rfc3339_opts(SecondsFormat::Millis, true))
+            }
+            _ => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<Tz: TimeZone> Serialize for Timestamp<'_, Option<DateTime<Tz>>>
+where
+    Tz::Offset: Display,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_ref().map(Timestamp).serialize(serializer)
+    }
+}
+
+/// For `#[serde(serialize_with = "timestamp")]` on the `DateTime` fields of
+/// response models, so `JSON_TIMESTAMP_PRECISION` applies to them.
+pub fn timestamp<T, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Timestamp<'a, T>: Serialize,
+{
+    Timestamp(value).serialize(serializer)
+}
+
+pub fn to_camel_case(name: &str) -> String {
+    let mut parts = name.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+pub fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, FixedOffset};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Body {
+        #[serde(serialize_with = "timestamp")]
+        due_at: DateTime<FixedOffset>,
+        title: String,
+        subtasks: Vec<Subtask>,
+        labels: HashMap<String, String>,
+        expression: Value,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Subtask {
+        #[serde(serialize_with = "timestamp")]
+        completed_at: Option<DateTime<FixedOffset>>,
+    }
+
+    fn body() -> Body {
+        Body {
+            due_at: DateTime::parse_from_rfc3339("2025-03-01T09:30:00.123456+00:00").unwrap(),
+            title: "2025-03-01T09:30:00.123456+00:00".to_string(),
+            subtasks: vec![Subtask {
+                completed_at: DateTime::parse_from_rfc3339("2025-03-01T11:00:00+02:00").ok(),
+            }],
+            labels: HashMap::from([("due_at".to_string(), "x".to_string())]),
+            expression: json!({ "project_id": null }),
+        }
+    }
+
+    #[test]
+    fn test_camel_case_policy_renames_fields_and_trims_timestamps() {
+        let policy = JsonPolicy {
+            naming: FieldNaming::CamelCase,
+            timestamps: TimestampPrecision::Millis,
+        };
+
+        let bytes = policy.to_vec(&body()).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Value>(&bytes).unwrap(),
+            json!({
+                "dueAt": "2025-03-01T09:30:00.123Z",
+                "title": "2025-03-01T09:30:00.123456+00:00",
+                "subtasks": [{ "completedAt": "2025-03-01T11:00:00.000+02:00" }],
+                "labels": { "due_at": "x" },
+                "expression": { "project_id": null },
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_policy_leaves_output_unchanged() {
+        let body = body();
+
+        let bytes = JsonPolicy::default().to_vec(&body).unwrap();
+
+        assert_eq!(bytes, serde_json::to_vec(&body).unwrap());
+    }
+
+    #[test]
+    fn test_any_case_accepts_camel_case_fields_only() {
+        let AnyCase(body) = serde_json::from_value::<AnyCase<Body>>(json!({
+            "dueAt": "2025-03-01T09:30:00Z",
+            "title": "Ship",
+            "subtasks": [{ "completedAt": null }],
+            "labels": { "dueAt": "x" },
+            "expression": { "projectId": null },
+        }))
+        .unwrap();
+
+        assert_eq!(body.due_at.to_rfc3339(), "2025-03-01T09:30:00+00:00");
+        assert_eq!(body.labels["dueAt"], "x");
+        assert_eq!(body.expression, json!({ "projectId": null }));
+    }
+
+    #[test]
+    fn test_case_conversions() {
+        assert_eq!(to_camel_case("description_html"), "descriptionHtml");
+        assert_eq!(to_camel_case("id"), "id");
+        assert_eq!(to_snake_case("descriptionHtml"), "description_html");
+        assert_eq!(to_snake_case("due_at"), "due_at");
+    }
+}
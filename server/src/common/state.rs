@@ -2,18 +2,78 @@ use std::sync::Arc;
 
 use sea_orm::DatabaseConnection;
 
-use crate::todo::{repository::TodoRepositoryImpl, service::TodoService};
+use crate::calendar::{repository::CalendarRepositoryImpl, service::CalendarService};
+use crate::config::Config;
+use crate::job::{repository::JobRepositoryImpl, service::JobService};
+use crate::mail::{repository::EmailRepositoryImpl, service::EmailService};
+use crate::notification::{repository::NotificationRepositoryImpl, service::NotificationService};
+use crate::outbox::{service::OutboxService, sink::EventBus};
+use crate::saved_filter::{repository::SavedFilterRepositoryImpl, service::SavedFilterService};
+use crate::share::{repository::ShareRepositoryImpl, service::ShareService};
+use crate::time_entry::{repository::TimeEntryRepositoryImpl, service::TimeEntryService};
+use crate::todo::{
+    repository::TodoRepositoryImpl, service::TodoService, status::StatusTransitions,
+};
+use crate::todo_template::{repository::TemplateRepositoryImpl, service::TemplateService};
+
+const EVENT_BUS_CAPACITY: usize = 1024;
 
 #[derive(Clone)]
 pub struct AppState {
     pub todo_service: TodoService<TodoRepositoryImpl>,
+    pub job_service: JobService<JobRepositoryImpl>,
+    pub calendar_service: CalendarService<CalendarRepositoryImpl>,
+    pub time_entry_service: TimeEntryService<TimeEntryRepositoryImpl>,
+    pub saved_filter_service: SavedFilterService<SavedFilterRepositoryImpl>,
+    pub share_service: ShareService<ShareRepositoryImpl>,
+    pub notification_service: NotificationService<NotificationRepositoryImpl>,
+    pub email_service: EmailService<EmailRepositoryImpl>,
+    pub template_service: TemplateService<TemplateRepositoryImpl>,
+    pub outbox_service: OutboxService,
+    pub event_bus: EventBus,
 }
 
 impl AppState {
-    pub fn new(db: DatabaseConnection) -> Self {
-        let todo_repo = Arc::new(TodoRepositoryImpl { db });
-        let todo_service = TodoService::new(todo_repo);
+    pub fn new(db: DatabaseConnection, config: &Config) -> Self {
+        let todo_repo = Arc::new(TodoRepositoryImpl { db: db.clone() });
+        let transitions = StatusTransitions::parse(&config.todo_status_transitions)
+            .expect("Invalid TODO_STATUS_TRANSITIONS");
+        let todo_service = TodoService::new(
+            todo_repo,
+            Arc::new(transitions),
+            config.todo_description_max_length,
+        );
+        let job_repo = Arc::new(JobRepositoryImpl { db: db.clone() });
+        let job_service = JobService::new(job_repo, config.job_max_attempts);
+        let calendar_repo = Arc::new(CalendarRepositoryImpl { db: db.clone() });
+        let calendar_service = CalendarService::new(calendar_repo);
+        let time_entry_repo = Arc::new(TimeEntryRepositoryImpl { db: db.clone() });
+        let time_entry_service = TimeEntryService::new(time_entry_repo);
+        let saved_filter_repo = Arc::new(SavedFilterRepositoryImpl { db: db.clone() });
+        let saved_filter_service = SavedFilterService::new(saved_filter_repo);
+        let share_repo = Arc::new(ShareRepositoryImpl { db: db.clone() });
+        let share_service = ShareService::new(share_repo);
+        let notification_repo = Arc::new(NotificationRepositoryImpl { db: db.clone() });
+        let notification_service = NotificationService::new(notification_repo);
+        let email_repo = Arc::new(EmailRepositoryImpl { db: db.clone() });
+        let email_service = EmailService::new(email_repo);
+        let template_repo = Arc::new(TemplateRepositoryImpl { db: db.clone() });
+        let template_service = TemplateService::new(template_repo);
+        let outbox_service = OutboxService::new(db, config.outbox_max_attempts);
+        let event_bus = EventBus::new(EVENT_BUS_CAPACITY);
 
-        Self { todo_service }
+        Self {
+            todo_service,
+            job_service,
+            calendar_service,
+            time_entry_service,
+            saved_filter_service,
+            share_service,
+            notification_service,
+            email_service,
+            template_service,
+            outbox_service,
+            event_bus,
+        }
     }
 }
@@ -0,0 +1,33 @@
+use migration::MigratorTrait;
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection};
+use uuid::Uuid;
+
+/// A migrated database in a schema of its own, so tests can run in parallel.
+/// Returns `None` when `TEST_DATABASE_URL` is unset; tests then skip.
+pub async fn connect() -> Option<DatabaseConnection> {
+    let url = std::env::var("TEST_DATABASE_URL").ok()?;
+    let schema = format!("test_{}", Uuid::new_v4().simple());
+
+    let admin = Database::connect(&url)
+        .await
+        .expect("connect to TEST_DATABASE_URL");
+    admin
+        .execute_unprepared(&format!("CREATE SCHEMA {schema}"))
+        .await
+        .expect("create test schema");
+    admin.close().await.expect("close admin connection");
+
+    let mut options = ConnectOptions::new(url);
+    options
+        .set_schema_search_path(schema)
+        .max_connections(4)
+        .sqlx_logging(false);
+    let db = Database::connect(options)
+        .await
+        .expect("connect to test schema");
+    migration::Migrator::up(&db, None)
+        .await
+        .expect("run migrations");
+
+    Some(db)
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+/// Generates an unguessable URL-safe secret (two random UUIDs, 244 bits).
+pub fn generate() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_is_hex_and_unique() {
+        let a = generate();
+        let b = generate();
+
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+}
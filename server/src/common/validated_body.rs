@@ -0,0 +1,143 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::body::Bytes;
+use axum::extract::{rejection::JsonRejection, FromRequest, Json, Request};
+use serde::de::DeserializeOwned;
+use tracing::debug;
+use validator::Validate;
+
+use crate::common::error::ApiError;
+use crate::common::negotiate::{Format, NOT_ACCEPTABLE};
+use crate::common::serialization::AnyCase;
+
+/// A request body in JSON, MessagePack or CBOR, chosen by `Content-Type`,
+/// that passed `Validate`. Fields may be spelled in snake_case or camelCase.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedBody<T>(pub T);
+
+impl<T> Deref for ValidatedBody<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ValidatedBody<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequest<S> for ValidatedBody<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Refuse before the handler writes anything it could not report back.
+        if Format::current().is_none() {
+            return Err(ApiError::NotAcceptable(NOT_ACCEPTABLE));
+        }
+        let format = Format::from_content_type(req.headers()).ok_or_else(|| {
+            ApiError::BadRequest(
+                "Request must have Content-Type: application/json, application/msgpack or application/cbor"
+                    .to_string(),
+            )
+        })?;
+        let bytes = Bytes::from_request(req, state).await.map_err(|e| {
+            debug!("Body read error: {:?}", e);
+            ApiError::BadRequest("Invalid request body".to_string())
+        })?;
+
+        let AnyCase(value) = match format {
+            Format::Json => {
+                let Json(value) = Json::<AnyCase<T>>::from_bytes(&bytes).map_err(|e| {
+                    debug!("JSON parsing error: {:?}", e);
+                    map_json_rejection_to_user_error(e)
+                })?;
+                value
+            }
+            binary => binary.decode(&bytes).map_err(|e| {
+                debug!("{} parsing error: {}", binary.name(), e);
+                ApiError::BadRequest(format!("Invalid {} in request body", binary.name()))
+            })?,
+        };
+
+        value.validate()?;
+
+        Ok(ValidatedBody(value))
+    }
+}
+
+fn map_json_rejection_to_user_error(rejection: JsonRejection) -> ApiError {
+    use axum::extract::rejection::*;
+
+    match rejection {
+        JsonRejection::JsonDataError(_) => {
+            ApiError::BadRequest("Invalid JSON format in request body".to_string())
+        }
+        JsonRejection::JsonSyntaxError(_) => {
+            ApiError::BadRequest("Malformed JSON in request body".to_string())
+        }
+        JsonRejection::BytesRejection(_) => {
+            ApiError::BadRequest("Invalid request body".to_string())
+        }
+        _ => ApiError::BadRequest("Invalid request format".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, Validate)]
+    struct Payload {
+        #[validate(length(min = 1))]
+        title: String,
+    }
+
+    fn request(content_type: &str, body: Vec<u8>) -> Request {
+        Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decodes_binary_formats() {
+        let payload = Payload {
+            title: "Ship".to_string(),
+        };
+        for format in [Format::MessagePack, Format::Cbor] {
+            let req = request(format.content_type(), format.encode(&payload).unwrap());
+
+            let ValidatedBody(decoded) = ValidatedBody::<Payload>::from_request(req, &())
+                .await
+                .unwrap();
+
+            assert_eq!(decoded.title, "Ship");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validates_and_rejects_unknown_content_types() {
+        let empty = Format::Cbor
+            .encode(&Payload {
+                title: String::new(),
+            })
+            .unwrap();
+        let invalid =
+            ValidatedBody::<Payload>::from_request(request("application/cbor", empty), &()).await;
+        let unsupported =
+            ValidatedBody::<Payload>::from_request(request("text/plain", b"Ship".to_vec()), &())
+                .await;
+
+        assert!(matches!(invalid, Err(ApiError::BadRequest(_))));
+        assert!(matches!(unsupported, Err(ApiError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,56 @@
+use axum::extract::{rejection::QueryRejection, FromRequestParts, Query};
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+use tracing::debug;
+use validator::Validate;
+
+use crate::common::error::ApiError;
+use crate::common::serialization::AnyCase;
+
+/// Query parameters that passed `Validate`, named in snake_case or camelCase.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> std::ops::Deref for ValidatedQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for ValidatedQuery<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate + Send + Sync,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(AnyCase(value)) = Query::<AnyCase<T>>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| {
+                debug!("Query parsing error: {:?}", rejection);
+                map_query_rejection_to_user_error(rejection)
+            })?;
+
+        value.validate()?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
+
+fn map_query_rejection_to_user_error(rejection: QueryRejection) -> ApiError {
+    match rejection {
+        QueryRejection::FailedToDeserializeQueryString(inner) => {
+            ApiError::BadRequest(inner.body_text())
+        }
+        _ => ApiError::BadRequest("Invalid query parameters".to_string()),
+    }
+}
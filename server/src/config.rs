@@ -1,7 +1,13 @@
+use chrono::{DateTime, Utc};
 use dotenvy::dotenv;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 
+use crate::common::serialization::{FieldNaming, TimestampPrecision};
+use crate::mail::mailer::MailTransportKind;
+use crate::outbox::sink::OutboxSinkKind;
+use crate::todo::status::DEFAULT_TRANSITIONS;
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub database_url: String,
@@ -9,6 +15,174 @@ pub struct Config {
     pub jwks_uri: String,
     pub jwt_audience: String,
     pub jwt_issuer: String,
+    /// JWT subjects allowed on the `/admin` routes.
+    #[serde(default)]
+    pub admin_subjects: Vec<String>,
+    #[serde(default = "default_outbox_sinks")]
+    pub outbox_sinks: Vec<OutboxSinkKind>,
+    pub outbox_webhook_url: Option<String>,
+    #[serde(default = "default_outbox_poll_interval_ms")]
+    pub outbox_poll_interval_ms: u64,
+    #[serde(default = "default_outbox_batch_size")]
+    pub outbox_batch_size: u64,
+    #[serde(default = "default_outbox_max_attempts")]
+    pub outbox_max_attempts: i32,
+    #[serde(default = "default_outbox_backoff_base_secs")]
+    pub outbox_backoff_base_secs: u64,
+    #[serde(default = "default_outbox_lease_secs")]
+    pub outbox_lease_secs: i64,
+    #[serde(default = "default_outbox_retention_hours")]
+    pub outbox_retention_hours: i64,
+    #[serde(default = "default_job_workers")]
+    pub job_workers: usize,
+    #[serde(default = "default_job_poll_interval_ms")]
+    pub job_poll_interval_ms: u64,
+    #[serde(default = "default_job_max_attempts")]
+    pub job_max_attempts: i32,
+    #[serde(default = "default_job_backoff_base_secs")]
+    pub job_backoff_base_secs: u64,
+    #[serde(default = "default_job_lock_timeout_secs")]
+    pub job_lock_timeout_secs: u64,
+    #[serde(default = "default_job_retention_days")]
+    pub job_retention_days: i64,
+    #[serde(default = "default_todo_archive_after_days")]
+    pub todo_archive_after_days: i64,
+    #[serde(default = "default_todo_archive_batch_size")]
+    pub todo_archive_batch_size: u64,
+    #[serde(default = "default_todo_status_transitions")]
+    pub todo_status_transitions: String,
+    #[serde(default = "default_todo_description_max_length")]
+    pub todo_description_max_length: usize,
+    #[serde(default = "default_notification_overdue_lookback_hours")]
+    pub notification_overdue_lookback_hours: i64,
+    #[serde(default = "default_mail_transport")]
+    pub mail_transport: MailTransportKind,
+    #[serde(default = "default_mail_from")]
+    pub mail_from: String,
+    #[serde(default = "default_mail_file_dir")]
+    pub mail_file_dir: String,
+    #[serde(default = "default_smtp_host")]
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default = "default_smtp_tls")]
+    pub smtp_tls: bool,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    #[serde(default = "default_reminder_batch_size")]
+    pub reminder_batch_size: u64,
+    #[serde(default)]
+    pub graphiql_enabled: bool,
+    #[serde(default)]
+    pub swagger_ui_enabled: bool,
+    /// Serves gRPC on this port instead of the HTTP listener.
+    pub grpc_port: Option<u16>,
+    pub api_v1_deprecated_at: Option<DateTime<Utc>>,
+    pub api_v1_sunset_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub json_field_naming: FieldNaming,
+    #[serde(default)]
+    pub json_timestamp_precision: TimestampPrecision,
+}
+
+fn default_outbox_sinks() -> Vec<OutboxSinkKind> {
+    vec![OutboxSinkKind::Log]
+}
+
+fn default_outbox_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_outbox_batch_size() -> u64 {
+    100
+}
+
+fn default_outbox_max_attempts() -> i32 {
+    10
+}
+
+fn default_outbox_backoff_base_secs() -> u64 {
+    5
+}
+
+fn default_outbox_lease_secs() -> i64 {
+    60
+}
+
+fn default_outbox_retention_hours() -> i64 {
+    168
+}
+
+fn default_job_workers() -> usize {
+    2
+}
+
+fn default_job_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_job_max_attempts() -> i32 {
+    5
+}
+
+fn default_job_backoff_base_secs() -> u64 {
+    10
+}
+
+fn default_job_lock_timeout_secs() -> u64 {
+    300
+}
+
+fn default_job_retention_days() -> i64 {
+    7
+}
+
+fn default_todo_archive_after_days() -> i64 {
+    30
+}
+
+fn default_todo_archive_batch_size() -> u64 {
+    500
+}
+
+fn default_todo_status_transitions() -> String {
+    DEFAULT_TRANSITIONS.to_string()
+}
+
+fn default_todo_description_max_length() -> usize {
+    10_000
+}
+
+fn default_notification_overdue_lookback_hours() -> i64 {
+    24
+}
+
+fn default_mail_transport() -> MailTransportKind {
+    MailTransportKind::Log
+}
+
+fn default_mail_from() -> String {
+    "Todos <noreply@localhost>".to_string()
+}
+
+fn default_mail_file_dir() -> String {
+    "mail".to_string()
+}
+
+fn default_smtp_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_tls() -> bool {
+    true
+}
+
+fn default_reminder_batch_size() -> u64 {
+    100
 }
 
 pub static CONFIG: Lazy<Config> = Lazy::new(|| {
@@ -0,0 +1,36 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, response::Html, Extension};
+
+use crate::common::{jwt::Claims, state::AppState};
+
+use super::loader::{SubtaskLoader, TagLoader};
+use super::schema::SCHEMA;
+
+pub async fn execute(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    // Loaders are per request so batches never serve another caller's data.
+    let service = state.todo_service;
+    let request = request
+        .into_inner()
+        .data(DataLoader::new(
+            TagLoader::new(service.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            SubtaskLoader::new(service.clone()),
+            tokio::spawn,
+        ))
+        .data(service)
+        .data(claims);
+
+    SCHEMA.execute(request).await.into()
+}
+
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
@@ -0,0 +1,42 @@
+use async_graphql::{Error, ErrorExtensions};
+
+use crate::common::error::ApiError;
+
+/// Converts an error into a GraphQL error whose `extensions.code` is one of
+/// `UNAUTHENTICATED`, `FORBIDDEN`, `NOT_FOUND`, `BAD_USER_INPUT`, `CONFLICT`
+/// or `INTERNAL_SERVER_ERROR`, following the REST status mapping.
+pub fn to_graphql_error(err: impl Into<ApiError>) -> Error {
+    let err = err.into();
+    let code = match &err {
+        ApiError::Unauthorized(_) => "UNAUTHENTICATED",
+        ApiError::Forbidden(_) => "FORBIDDEN",
+        ApiError::NotFound(_) => "NOT_FOUND",
+        ApiError::BadRequest(_) | ApiError::NotAcceptable(_) => "BAD_USER_INPUT",
+        ApiError::Conflict(_) => "CONFLICT",
+        ApiError::Internal(_) => "INTERNAL_SERVER_ERROR",
+    };
+
+    Error::new(err.client_msg()).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ServiceError;
+    use async_graphql::Value;
+
+    #[test]
+    fn test_service_errors_map_to_stable_codes() {
+        let cases = vec![
+            (ServiceError::NotFound, "NOT_FOUND"),
+            (ServiceError::ValidationError, "BAD_USER_INPUT"),
+            (ServiceError::Conflict("blocked"), "CONFLICT"),
+        ];
+
+        for (service_err, expected) in cases {
+            let err = to_graphql_error(service_err);
+            let code = err.extensions.as_ref().and_then(|ext| ext.get("code"));
+            assert_eq!(code, Some(&Value::from(expected)));
+        }
+    }
+}
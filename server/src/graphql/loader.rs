@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use async_graphql::dataloader::Loader;
+use async_graphql::Error;
+use entity::todo::Model;
+use uuid::Uuid;
+
+use crate::todo::{repository::TodoRepositoryImpl, service::TodoService};
+
+use super::error::to_graphql_error;
+
+/// Batches `Todo.tags` lookups of one request into a single query.
+pub struct TagLoader {
+    service: TodoService<TodoRepositoryImpl>,
+}
+
+impl TagLoader {
+    pub fn new(service: TodoService<TodoRepositoryImpl>) -> Self {
+        Self { service }
+    }
+}
+
+impl Loader<Uuid> for TagLoader {
+    type Value = Vec<String>;
+    type Error = Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        self.service
+            .tags_of(keys.to_vec())
+            .await
+            .map_err(to_graphql_error)
+    }
+}
+
+/// Batches `Todo.subtasks` lookups of one request into a single query.
+pub struct SubtaskLoader {
+    service: TodoService<TodoRepositoryImpl>,
+}
+
+impl SubtaskLoader {
+    pub fn new(service: TodoService<TodoRepositoryImpl>) -> Self {
+        Self { service }
+    }
+}
+
+impl Loader<Uuid> for SubtaskLoader {
+    type Value = Vec<Model>;
+    type Error = Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        self.service
+            .subtasks_of(keys.to_vec())
+            .await
+            .map_err(to_graphql_error)
+    }
+}
@@ -0,0 +1,218 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, Enum, InputObject, Object, Result};
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::sea_orm_active_enums::TodoStatus;
+use entity::todo::Model;
+use uuid::Uuid;
+
+use crate::todo::filter::FilterExpression;
+use crate::todo::markdown;
+use crate::todo::model::{CreateTodoRequest, Priority, UpdateTodoRequest};
+use crate::todo::status::TodoState;
+
+use super::loader::{SubtaskLoader, TagLoader};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "TodoStatus", remote = "crate::todo::status::TodoState")]
+pub enum Status {
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "Priority", remote = "crate::todo::model::Priority")]
+pub enum PriorityLevel {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+/// The GraphQL view of a todo; mirrors the REST `TodoResponse`.
+pub struct Todo(pub Model);
+
+#[Object]
+impl Todo {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    /// Sequential among the owner's todos.
+    async fn number(&self) -> i32 {
+        self.0.number
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// Markdown source.
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    /// Sanitized HTML rendering of `description`.
+    async fn description_html(&self) -> Option<String> {
+        self.0.description.as_deref().map(markdown::to_safe_html)
+    }
+
+    async fn status(&self) -> Status {
+        TodoState::from(self.0.status).into()
+    }
+
+    async fn completed(&self) -> bool {
+        self.0.status == TodoStatus::Done
+    }
+
+    async fn completed_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.0.completed_at
+    }
+
+    async fn archived_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.0.archived_at
+    }
+
+    async fn project_id(&self) -> Option<Uuid> {
+        self.0.project_id
+    }
+
+    async fn due_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.0.due_at
+    }
+
+    async fn priority(&self) -> Option<PriorityLevel> {
+        self.0
+            .priority
+            .map(|priority| Priority::from(priority).into())
+    }
+
+    /// Sum of finished time entries; a running timer is not included.
+    async fn tracked_seconds(&self) -> i64 {
+        self.0.tracked_seconds
+    }
+
+    async fn assignee_id(&self) -> Option<&str> {
+        self.0.assignee_id.as_deref()
+    }
+
+    async fn remind_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.0.remind_at
+    }
+
+    async fn reminded_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.0.reminded_at
+    }
+
+    async fn parent_id(&self) -> Option<Uuid> {
+        self.0.parent_id
+    }
+
+    /// Order among the parent's subtasks.
+    async fn position(&self) -> Option<i32> {
+        self.0.position
+    }
+
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        let loader = ctx.data::<DataLoader<TagLoader>>()?;
+        Ok(loader.load_one(self.0.id).await?.unwrap_or_default())
+    }
+
+    async fn subtasks(&self, ctx: &Context<'_>) -> Result<Vec<Todo>> {
+        let loader = ctx.data::<DataLoader<SubtaskLoader>>()?;
+        let subtasks = loader.load_one(self.0.id).await?.unwrap_or_default();
+        Ok(subtasks.into_iter().map(Todo).collect())
+    }
+}
+
+/// Same fields as the list query string, with lists as arrays.
+#[derive(Debug, Default, InputObject)]
+pub struct TodoFilterInput {
+    #[graphql(default)]
+    pub archived: bool,
+    #[graphql(default)]
+    pub status: Vec<Status>,
+    #[graphql(default)]
+    pub priority: Vec<PriorityLevel>,
+    #[graphql(default)]
+    pub tags: Vec<String>,
+    pub project_id: Option<Uuid>,
+    pub due_after: Option<String>,
+    pub due_before: Option<String>,
+    #[graphql(default)]
+    pub utc_offset_minutes: i32,
+}
+
+impl From<TodoFilterInput> for FilterExpression {
+    fn from(input: TodoFilterInput) -> Self {
+        Self {
+            archived: input.archived,
+            status: input.status.into_iter().map(Into::into).collect(),
+            priority: input.priority.into_iter().map(Into::into).collect(),
+            tags: input.tags,
+            project_id: input.project_id,
+            due_after: input.due_after,
+            due_before: input.due_before,
+            utc_offset_minutes: input.utc_offset_minutes,
+        }
+    }
+}
+
+#[derive(Debug, InputObject)]
+pub struct CreateTodoInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<PriorityLevel>,
+    pub remind_at: Option<DateTime<Utc>>,
+    #[graphql(default)]
+    pub tags: Vec<String>,
+}
+
+impl From<CreateTodoInput> for CreateTodoRequest {
+    fn from(input: CreateTodoInput) -> Self {
+        Self {
+            title: input.title,
+            description: input.description,
+            project_id: input.project_id,
+            due_at: input.due_at,
+            priority: input.priority.map(Into::into),
+            remind_at: input.remind_at,
+            tags: input.tags,
+        }
+    }
+}
+
+#[derive(Debug, InputObject)]
+pub struct UpdateTodoInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<Status>,
+    /// Legacy flag, ignored when `status` is given.
+    pub completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<PriorityLevel>,
+    pub remind_at: Option<DateTime<Utc>>,
+    /// Replaces the tags when given.
+    pub tags: Option<Vec<String>>,
+}
+
+impl From<UpdateTodoInput> for UpdateTodoRequest {
+    fn from(input: UpdateTodoInput) -> Self {
+        Self {
+            title: input.title,
+            description: input.description,
+            status: input.status.map(Into::into),
+            completed: input.completed,
+            project_id: input.project_id,
+            due_at: input.due_at,
+            priority: input.priority.map(Into::into),
+            remind_at: input.remind_at,
+            tags: input.tags,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use axum::{middleware, routing::post, Router};
+
+use crate::{
+    common::state::AppState, config::CONFIG, graphql::controller,
+    middleware::auth::is_authenticated,
+};
+
+pub fn init() -> Router<AppState> {
+    // Queries need a JWT like the REST todo routes. The GraphiQL page itself
+    // does not; its queries carry the token set in its headers editor.
+    let mut route = post(controller::execute).layer(middleware::from_fn(is_authenticated));
+    // The GraphiQL IDE is for development; production only accepts POST.
+    if CONFIG.graphiql_enabled {
+        route = route.get(controller::graphiql);
+    }
+
+    Router::new().route("/graphql", route)
+}
@@ -0,0 +1,177 @@
+use async_graphql::{Context, EmptySubscription, Object, Result, Schema};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use tracing::error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::common::error::ApiError;
+use crate::common::jwt::Claims;
+use crate::todo::filter::FilterExpression;
+use crate::todo::model::{CreateTodoRequest, UpdateTodoRequest};
+use crate::todo::{repository::TodoRepositoryImpl, service::TodoService};
+
+use super::error::to_graphql_error;
+use super::model::{CreateTodoInput, Todo, TodoFilterInput, UpdateTodoInput};
+
+/// Deep enough for todos with a few levels of subtasks.
+const MAX_DEPTH: usize = 10;
+
+/// Fields per query, so one request cannot fan out into unbounded work.
+const MAX_COMPLEXITY: usize = 250;
+
+pub type TodoSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Built once; per-request services, loaders and claims are attached as
+/// request data.
+pub static SCHEMA: Lazy<TodoSchema> = Lazy::new(|| {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+});
+
+fn todo_service<'a>(ctx: &Context<'a>) -> Result<&'a TodoService<TodoRepositoryImpl>> {
+    ctx.data::<TodoService<TodoRepositoryImpl>>()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn todo(&self, ctx: &Context<'_>, id: Uuid) -> Result<Todo> {
+        let todo = todo_service(ctx)?.get_todo_by_id(id).await.map_err(|err| {
+            error!(todo_id = %id, "Failed to get todo by id: {:?}", err);
+            to_graphql_error(err)
+        })?;
+
+        Ok(Todo(todo))
+    }
+
+    async fn todos(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: TodoFilterInput,
+    ) -> Result<Vec<Todo>> {
+        let filter = FilterExpression::from(filter)
+            .resolve(Utc::now())
+            .map_err(|err| to_graphql_error(ApiError::BadRequest(err)))?;
+        let todos = todo_service(ctx)?
+            .get_all_todos(filter)
+            .await
+            .map_err(|err| {
+                error!("Failed to get all todos: {:?}", err);
+                to_graphql_error(err)
+            })?;
+
+        Ok(todos.into_iter().map(Todo).collect())
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_todo(&self, ctx: &Context<'_>, input: CreateTodoInput) -> Result<Todo> {
+        let request = CreateTodoRequest::from(input);
+        request.validate().map_err(to_graphql_error)?;
+
+        let owner_id = Some(ctx.data::<Claims>()?.sub.clone());
+        let todo = todo_service(ctx)?
+            .create_todo(request.into_new_todo(owner_id))
+            .await
+            .map_err(|err| {
+                error!("Failed to create todo: {:?}", err);
+                to_graphql_error(err)
+            })?;
+
+        Ok(Todo(todo))
+    }
+
+    async fn update_todo(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateTodoInput,
+    ) -> Result<Todo> {
+        let request = UpdateTodoRequest::from(input);
+        request.validate().map_err(to_graphql_error)?;
+
+        let owner_id = &ctx.data::<Claims>()?.sub;
+        let service = todo_service(ctx)?;
+        service
+            .check_owner(id, owner_id)
+            .await
+            .map_err(to_graphql_error)?;
+        let todo = service
+            .update_todo(id, request.into())
+            .await
+            .map_err(|err| {
+                error!(todo_id = %id, "Failed to update todo: {:?}", err);
+                to_graphql_error(err)
+            })?;
+
+        Ok(Todo(todo))
+    }
+
+    async fn delete_todo(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+        let owner_id = &ctx.data::<Claims>()?.sub;
+        let service = todo_service(ctx)?;
+        service
+            .check_owner(id, owner_id)
+            .await
+            .map_err(to_graphql_error)?;
+        service.delete_todo(id).await.map_err(|err| {
+            error!(todo_id = %id, "Failed to delete todo: {:?}", err);
+            to_graphql_error(err)
+        })?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::Value;
+
+    #[test]
+    fn test_schema_exposes_todo_operations() {
+        let sdl = SCHEMA.sdl();
+
+        for operation in [
+            "todo(id: UUID!)",
+            "todos(",
+            "createTodo(",
+            "updateTodo(",
+            "deleteTodo(",
+        ] {
+            assert!(sdl.contains(operation), "{operation}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_todo_reuses_request_validation() {
+        let response = SCHEMA
+            .execute(r#"mutation { createTodo(input: { title: "" }) { id } }"#)
+            .await;
+
+        let code = response.errors[0]
+            .extensions
+            .as_ref()
+            .and_then(|ext| ext.get("code"));
+        assert_eq!(code, Some(&Value::from("BAD_USER_INPUT")));
+    }
+
+    #[tokio::test]
+    async fn test_complex_queries_are_rejected() {
+        let fields: String = (0..MAX_COMPLEXITY)
+            .map(|i| format!("f{i}: title "))
+            .collect();
+        let query = ["{ todos { ", &fields, "} }"].concat();
+
+        let response = SCHEMA.execute(query).await;
+
+        assert!(response.errors[0].message.contains("too complex"));
+    }
+}
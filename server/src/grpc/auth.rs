@@ -0,0 +1,44 @@
+use tonic::metadata::MetadataMap;
+use tonic::Status;
+
+use crate::common::error::ApiError;
+use crate::common::jwt::{self, Claims};
+
+use super::error::to_status;
+
+/// Validates the `authorization: Bearer <jwt>` metadata entry, which every
+/// call needs just as the REST and GraphQL todo routes need the header.
+/// Interceptors are synchronous, so each call awaits this itself.
+pub async fn authenticate(metadata: &MetadataMap) -> Result<Claims, Status> {
+    let token = extract_token(metadata).map_err(to_status)?;
+    jwt::validate(token).await.map_err(to_status)
+}
+
+fn extract_token(metadata: &MetadataMap) -> Result<&str, ApiError> {
+    metadata
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized("Missing token"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    #[test]
+    fn test_extract_token_from_metadata() {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("authorization", "Bearer abc".parse().unwrap());
+
+        assert_eq!(extract_token(&metadata).unwrap(), "abc");
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_unauthenticated() {
+        let status = authenticate(&MetadataMap::new()).await.unwrap_err();
+
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
+}
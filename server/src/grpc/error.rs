@@ -0,0 +1,37 @@
+use tonic::{Code, Status};
+
+use crate::common::error::ApiError;
+
+/// Converts an error into a gRPC status, following the REST status mapping.
+pub fn to_status(err: impl Into<ApiError>) -> Status {
+    let err = err.into();
+    let code = match &err {
+        ApiError::Unauthorized(_) => Code::Unauthenticated,
+        ApiError::Forbidden(_) => Code::PermissionDenied,
+        ApiError::NotFound(_) => Code::NotFound,
+        ApiError::BadRequest(_) | ApiError::NotAcceptable(_) => Code::InvalidArgument,
+        ApiError::Conflict(_) => Code::FailedPrecondition,
+        ApiError::Internal(_) => Code::Internal,
+    };
+
+    Status::new(code, err.client_msg())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ServiceError;
+
+    #[test]
+    fn test_service_errors_map_to_status_codes() {
+        let cases = vec![
+            (ServiceError::NotFound, Code::NotFound),
+            (ServiceError::ValidationError, Code::InvalidArgument),
+            (ServiceError::Conflict("blocked"), Code::FailedPrecondition),
+        ];
+
+        for (service_err, expected) in cases {
+            assert_eq!(to_status(service_err).code(), expected);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{DateTime, TimeZone, Utc};
+use entity::sea_orm_active_enums::TodoStatus;
+use entity::todo::Model;
+use prost_types::Timestamp;
+use tonic::Status;
+use uuid::Uuid;
+
+use crate::todo::filter::FilterExpression;
+use crate::todo::model::{CreateTodoRequest, Priority, UpdateTodoRequest};
+use crate::todo::status::TodoState;
+
+use super::proto;
+
+impl From<Model> for proto::Todo {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id.to_string(),
+            number: model.number,
+            title: model.title,
+            description: model.description,
+            status: proto::TodoStatus::from(TodoState::from(model.status)).into(),
+            completed: model.status == TodoStatus::Done,
+            completed_at: model.completed_at.map(timestamp),
+            archived_at: model.archived_at.map(timestamp),
+            project_id: model.project_id.map(|id| id.to_string()),
+            due_at: model.due_at.map(timestamp),
+            priority: model
+                .priority
+                .map(|priority| proto::Priority::from(Priority::from(priority)))
+                .unwrap_or_default()
+                .into(),
+            tracked_seconds: model.tracked_seconds,
+            assignee_id: model.assignee_id,
+            remind_at: model.remind_at.map(timestamp),
+            reminded_at: model.reminded_at.map(timestamp),
+            parent_id: model.parent_id.map(|id| id.to_string()),
+            position: model.position,
+        }
+    }
+}
+
+impl TryFrom<proto::ListTodosRequest> for FilterExpression {
+    type Error = Status;
+
+    fn try_from(request: proto::ListTodosRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            archived: request.archived,
+            status: request.status().filter_map(state).collect(),
+            priority: request.priority().filter_map(priority).collect(),
+            tags: request.tags,
+            project_id: request
+                .project_id
+                .as_deref()
+                .map(|id| parse_uuid("project_id", id))
+                .transpose()?,
+            due_after: request.due_after,
+            due_before: request.due_before,
+            utc_offset_minutes: request.utc_offset_minutes,
+        })
+    }
+}
+
+impl TryFrom<proto::CreateTodoRequest> for CreateTodoRequest {
+    type Error = Status;
+
+    fn try_from(request: proto::CreateTodoRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            priority: priority(request.priority()),
+            title: request.title,
+            description: request.description,
+            project_id: request
+                .project_id
+                .as_deref()
+                .map(|id| parse_uuid("project_id", id))
+                .transpose()?,
+            due_at: request.due_at.map(datetime).transpose()?,
+            remind_at: request.remind_at.map(datetime).transpose()?,
+            tags: request.tags,
+        })
+    }
+}
+
+/// Splits an update into the todo id and the REST request it corresponds to.
+pub fn into_update(request: proto::UpdateTodoRequest) -> Result<(Uuid, UpdateTodoRequest), Status> {
+    let id = parse_uuid("id", &request.id)?;
+    let update = UpdateTodoRequest {
+        status: state(request.status()),
+        priority: priority(request.priority()),
+        title: request.title,
+        description: request.description,
+        completed: request.completed,
+        project_id: request
+            .project_id
+            .as_deref()
+            .map(|id| parse_uuid("project_id", id))
+            .transpose()?,
+        due_at: request.due_at.map(datetime).transpose()?,
+        remind_at: request.remind_at.map(datetime).transpose()?,
+        tags: None,
+    };
+
+    Ok((id, update))
+}
+
+pub fn parse_uuid(field: &str, value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|_| Status::invalid_argument(format!("invalid {field}")))
+}
+
+fn timestamp<Tz: TimeZone>(at: DateTime<Tz>) -> Timestamp {
+    Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn datetime(at: Timestamp) -> Result<DateTime<Utc>, Status> {
+    u32::try_from(at.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(at.seconds, nanos))
+        .ok_or_else(|| Status::invalid_argument("invalid timestamp"))
+}
+
+fn state(status: proto::TodoStatus) -> Option<TodoState> {
+    match status {
+        proto::TodoStatus::Unspecified => None,
+        proto::TodoStatus::Todo => Some(TodoState::Todo),
+        proto::TodoStatus::InProgress => Some(TodoState::InProgress),
+        proto::TodoStatus::Blocked => Some(TodoState::Blocked),
+        proto::TodoStatus::Done => Some(TodoState::Done),
+        proto::TodoStatus::Cancelled => Some(TodoState::Cancelled),
+    }
+}
+
+impl From<TodoState> for proto::TodoStatus {
+    fn from(state: TodoState) -> Self {
+        match state {
+            TodoState::Todo => proto::TodoStatus::Todo,
+            TodoState::InProgress => proto::TodoStatus::InProgress,
+            TodoState::Blocked => proto::TodoStatus::Blocked,
+            TodoState::Done => proto::TodoStatus::Done,
+            TodoState::Cancelled => proto::TodoStatus::Cancelled,
+        }
+    }
+}
+
+fn priority(priority: proto::Priority) -> Option<Priority> {
+    match priority {
+        proto::Priority::Unspecified => None,
+        proto::Priority::Low => Some(Priority::Low),
+        proto::Priority::Medium => Some(Priority::Medium),
+        proto::Priority::High => Some(Priority::High),
+        proto::Priority::Urgent => Some(Priority::Urgent),
+    }
+}
+
+impl From<Priority> for proto::Priority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => proto::Priority::Low,
+            Priority::Medium => proto::Priority::Medium,
+            Priority::High => proto::Priority::High,
+            Priority::Urgent => proto::Priority::Urgent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        let at = DateTime::parse_from_rfc3339("2024-03-06T15:30:00.25+02:00").unwrap();
+
+        assert_eq!(datetime(timestamp(at)).unwrap(), at.to_utc());
+        assert!(datetime(Timestamp {
+            seconds: 0,
+            nanos: -1
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_unspecified_enums_are_absent() {
+        let request = proto::UpdateTodoRequest {
+            id: Uuid::new_v4().to_string(),
+            title: "Ship".to_string(),
+            ..Default::default()
+        };
+
+        let (_, update) = into_update(request).unwrap();
+
+        assert!(update.status.is_none());
+        assert!(update.priority.is_none());
+    }
+
+    #[test]
+    fn test_invalid_ids_are_rejected() {
+        let request = proto::UpdateTodoRequest {
+            id: "42".to_string(),
+            ..Default::default()
+        };
+
+        let status = into_update(request).unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
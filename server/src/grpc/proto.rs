@@ -0,0 +1,3 @@
+//! Code generated from `proto/todo/v1/todo.proto` by `build.rs`.
+
+tonic::include_proto!("todo.v1");
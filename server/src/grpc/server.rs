@@ -0,0 +1,36 @@
+use std::error::Error;
+
+use axum::{serve::Serve, Router};
+use tokio::net::TcpListener;
+use tonic::server::NamedService;
+use tracing::info;
+
+use crate::common::state::AppState;
+
+use super::proto::todo_service_server::TodoServiceServer;
+use super::service::TodoGrpcService;
+
+/// The gRPC services as an axum router, to merge into the REST router or
+/// serve on their own port. Routed by hand because `tonic::service::Routes`
+/// installs a fallback that would answer unknown REST paths too.
+pub fn routes(state: AppState) -> Router {
+    let todos = TodoServiceServer::new(TodoGrpcService::new(state));
+
+    // The wildcard is appended rather than escaped inside `format!`, because
+    // cargo-generate would read a doubled brace as a template placeholder.
+    let path = format!("/{}/", TodoServiceServer::<TodoGrpcService>::NAME) + "{*method}";
+
+    Router::new().route_service(&path, todos)
+}
+
+pub async fn create(
+    routes: Router,
+    port: u16,
+) -> Result<Serve<TcpListener, Router, Router>, Box<dyn Error>> {
+    let tcp_listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    let address = tcp_listener.local_addr()?;
+
+    info!("gRPC listening on {}", address);
+
+    Ok(axum::serve(tcp_listener, routes))
+}
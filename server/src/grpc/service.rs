@@ -0,0 +1,144 @@
+use chrono::Utc;
+use tonic::{Request, Response, Status};
+use tracing::error;
+use validator::Validate;
+
+use crate::common::error::ApiError;
+use crate::common::state::AppState;
+use crate::todo::filter::FilterExpression;
+use crate::todo::model::CreateTodoRequest;
+
+use super::auth::authenticate;
+use super::error::to_status;
+use super::model::{into_update, parse_uuid};
+use super::proto::{self, todo_service_server::TodoService};
+
+/// Serves `todo.v1.TodoService` from the same `AppState` as the REST routes.
+#[derive(Clone)]
+pub struct TodoGrpcService {
+    state: AppState,
+}
+
+impl TodoGrpcService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl TodoService for TodoGrpcService {
+    async fn get_todo(
+        &self,
+        request: Request<proto::GetTodoRequest>,
+    ) -> Result<Response<proto::Todo>, Status> {
+        authenticate(request.metadata()).await?;
+        let id = parse_uuid("id", &request.get_ref().id)?;
+
+        let todo = self
+            .state
+            .todo_service
+            .get_todo_by_id(id)
+            .await
+            .map_err(|err| {
+                error!(todo_id = %id, "Failed to get todo by id: {:?}", err);
+                to_status(err)
+            })?;
+
+        Ok(Response::new(todo.into()))
+    }
+
+    async fn list_todos(
+        &self,
+        request: Request<proto::ListTodosRequest>,
+    ) -> Result<Response<proto::ListTodosResponse>, Status> {
+        authenticate(request.metadata()).await?;
+        let filter = FilterExpression::try_from(request.into_inner())?
+            .resolve(Utc::now())
+            .map_err(|err| to_status(ApiError::BadRequest(err)))?;
+
+        let todos = self
+            .state
+            .todo_service
+            .get_all_todos(filter)
+            .await
+            .map_err(|err| {
+                error!("Failed to get all todos: {:?}", err);
+                to_status(err)
+            })?;
+
+        Ok(Response::new(proto::ListTodosResponse {
+            todos: todos.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn create_todo(
+        &self,
+        request: Request<proto::CreateTodoRequest>,
+    ) -> Result<Response<proto::Todo>, Status> {
+        let claims = authenticate(request.metadata()).await?;
+        let request = CreateTodoRequest::try_from(request.into_inner())?;
+        request.validate().map_err(to_status)?;
+
+        let todo = self
+            .state
+            .todo_service
+            .create_todo(request.into_new_todo(Some(claims.sub)))
+            .await
+            .map_err(|err| {
+                error!("Failed to create todo: {:?}", err);
+                to_status(err)
+            })?;
+
+        Ok(Response::new(todo.into()))
+    }
+
+    async fn update_todo(
+        &self,
+        request: Request<proto::UpdateTodoRequest>,
+    ) -> Result<Response<proto::Todo>, Status> {
+        let claims = authenticate(request.metadata()).await?;
+        let (id, update) = into_update(request.into_inner())?;
+        update.validate().map_err(to_status)?;
+
+        self.state
+            .todo_service
+            .check_owner(id, &claims.sub)
+            .await
+            .map_err(to_status)?;
+        let todo = self
+            .state
+            .todo_service
+            .update_todo(id, update.into())
+            .await
+            .map_err(|err| {
+                error!(todo_id = %id, "Failed to update todo: {:?}", err);
+                to_status(err)
+            })?;
+
+        Ok(Response::new(todo.into()))
+    }
+
+    async fn delete_todo(
+        &self,
+        request: Request<proto::DeleteTodoRequest>,
+    ) -> Result<Response<proto::DeleteTodoResponse>, Status> {
+        let claims = authenticate(request.metadata()).await?;
+        let id = parse_uuid("id", &request.get_ref().id)?;
+
+        self.state
+            .todo_service
+            .check_owner(id, &claims.sub)
+            .await
+            .map_err(to_status)?;
+        self.state
+            .todo_service
+            .delete_todo(id)
+            .await
+            .map_err(|err| {
+                error!(todo_id = %id, "Failed to delete todo: {:?}", err);
+                to_status(err)
+            })?;
+
+        Ok(Response::new(proto::DeleteTodoResponse {}))
+    }
+}
@@ -0,0 +1,45 @@
+use axum::extract::State;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    common::error::ApiResult, common::negotiate::Negotiated, common::state::AppState,
+    common::validated_path::ValidatedPath, common::validated_query::ValidatedQuery,
+};
+
+use super::model::{JobResponse, ListJobsQuery};
+
+const DEFAULT_LIMIT: u64 = 100;
+
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListJobsQuery>,
+) -> ApiResult<Negotiated<Vec<JobResponse>>> {
+    let jobs = state
+        .job_service
+        .list_jobs(
+            query.status.map(Into::into),
+            query.limit.unwrap_or(DEFAULT_LIMIT),
+        )
+        .await
+        .map_err(|err| {
+            error!("Failed to list jobs: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        jobs.into_iter().map(JobResponse::from).collect(),
+    ))
+}
+
+pub async fn retry(
+    State(state): State<AppState>,
+    ValidatedPath(job_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<JobResponse>> {
+    let job = state.job_service.retry_job(job_id).await.map_err(|err| {
+        error!(job_id = %job_id, "Failed to retry job: {:?}", err);
+        err
+    })?;
+
+    Ok(Negotiated(JobResponse::from(job)))
+}
@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// A unit of background work. Implementations hold their own dependencies and
+/// receive the payload stored with the queued job.
+#[async_trait]
+pub trait Job: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn run(&self, payload: Value) -> anyhow::Result<()>;
+}
+
+#[derive(Default, Clone)]
+pub struct JobRegistry {
+    jobs: HashMap<&'static str, Arc<dyn Job>>,
+}
+
+impl JobRegistry {
+    pub fn register(&mut self, job: impl Job + 'static) {
+        self.jobs.insert(job.name(), Arc::new(job));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Job>> {
+        self.jobs.get(name).cloned()
+    }
+}
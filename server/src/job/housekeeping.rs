@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use tracing::info;
+
+use super::handler::Job;
+use super::repository::JobRepository;
+
+/// Deletes succeeded jobs once they are older than the retention window.
+/// Failed jobs are kept until they are retried or removed by hand.
+pub struct PurgeSucceededJobs<R: JobRepository> {
+    repo: Arc<R>,
+    retention: Duration,
+}
+
+impl<R: JobRepository> PurgeSucceededJobs<R> {
+    pub const NAME: &'static str = "jobs.purge_succeeded";
+
+    pub fn new(repo: Arc<R>, retention_days: i64) -> Self {
+        Self {
+            repo,
+            retention: Duration::days(retention_days),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: JobRepository> Job for PurgeSucceededJobs<R> {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn run(&self, _payload: Value) -> anyhow::Result<()> {
+        let purged = self
+            .repo
+            .purge_succeeded(Utc::now() - self.retention)
+            .await?;
+
+        info!(purged, "Purged succeeded jobs");
+        Ok(())
+    }
+}
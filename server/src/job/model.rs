@@ -0,0 +1,78 @@
+use chrono::{DateTime, FixedOffset};
+use entity::job::Model;
+use entity::sea_orm_active_enums::JobStatus;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::common::serialization::timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl From<JobStatus> for JobState {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Pending => JobState::Pending,
+            JobStatus::Running => JobState::Running,
+            JobStatus::Succeeded => JobState::Succeeded,
+            JobStatus::Failed => JobState::Failed,
+        }
+    }
+}
+
+impl From<JobState> for JobStatus {
+    fn from(state: JobState) -> Self {
+        match state {
+            JobState::Pending => JobStatus::Pending,
+            JobState::Running => JobStatus::Running,
+            JobState::Succeeded => JobStatus::Succeeded,
+            JobState::Failed => JobStatus::Failed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct JobResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub status: JobState,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    #[serde(serialize_with = "timestamp")]
+    pub run_at: DateTime<FixedOffset>,
+    pub last_error: Option<String>,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for JobResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            status: model.status.into(),
+            attempts: model.attempts,
+            max_attempts: model.max_attempts,
+            run_at: model.run_at,
+            last_error: model.last_error,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListJobsQuery {
+    pub status: Option<JobState>,
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<u64>,
+}
@@ -0,0 +1,315 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::job::{ActiveModel, Column, Entity, Model};
+use entity::sea_orm_active_enums::JobStatus;
+use sea_orm::sea_query::{LockBehavior, LockType, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct NewJob {
+    pub name: String,
+    pub payload: Value,
+    pub run_at: DateTime<Utc>,
+    pub max_attempts: i32,
+    /// Jobs sharing a dedupe key are only enqueued once.
+    pub dedupe_key: Option<String>,
+}
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    async fn enqueue(&self, job: NewJob) -> Result<Option<Model>, DbErr>;
+    async fn claim_due(&self, lock_timeout: Duration) -> Result<Option<Model>, DbErr>;
+    /// Renews the lock of a running job. This and the two calls below only
+    /// apply while the job is still running `attempt`, and return `false` once
+    /// another worker has claimed it again.
+    async fn heartbeat(&self, id: Uuid, attempt: i32) -> Result<bool, DbErr>;
+    async fn complete(&self, id: Uuid, attempt: i32) -> Result<bool, DbErr>;
+    async fn fail(
+        &self,
+        id: Uuid,
+        attempt: i32,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbErr>;
+    async fn get_by_id(&self, id: Uuid) -> Result<Model, DbErr>;
+    async fn list(&self, status: Option<JobStatus>, limit: u64) -> Result<Vec<Model>, DbErr>;
+    /// Requeues a failed job; `None` when it does not exist or is not failed.
+    async fn retry_failed(&self, id: Uuid) -> Result<Option<Model>, DbErr>;
+    async fn purge_succeeded(&self, before: DateTime<Utc>) -> Result<u64, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct JobRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl JobRepository for JobRepositoryImpl {
+    async fn enqueue(&self, job: NewJob) -> Result<Option<Model>, DbErr> {
+        let res = Entity::insert(ActiveModel {
+            name: Set(job.name),
+            payload: Set(job.payload),
+            max_attempts: Set(job.max_attempts),
+            run_at: Set(job.run_at.into()),
+            dedupe_key: Set(job.dedupe_key),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::column(Column::DedupeKey)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_with_returning(&self.db)
+        .await;
+
+        match res {
+            Ok(model) => Ok(Some(model)),
+            Err(DbErr::RecordNotInserted) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn claim_due(&self, lock_timeout: Duration) -> Result<Option<Model>, DbErr> {
+        let now = Utc::now();
+        let stale_before = now - lock_timeout;
+        let txn = self.db.begin().await?;
+
+        // Running jobs whose lock expired belong to a worker that died mid-run.
+        let job = Entity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(Column::Status.eq(JobStatus::Pending))
+                            .add(Column::RunAt.lte(now)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(Column::Status.eq(JobStatus::Running))
+                            .add(Column::LockedAt.lt(stale_before)),
+                    ),
+            )
+            .order_by_asc(Column::RunAt)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .one(&txn)
+            .await?;
+
+        let Some(job) = job else {
+            txn.commit().await?;
+            return Ok(None);
+        };
+
+        let attempts = job.attempts + 1;
+        let claimed = ActiveModel {
+            id: Set(job.id),
+            status: Set(JobStatus::Running),
+            attempts: Set(attempts),
+            locked_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(Some(claimed))
+    }
+
+    async fn heartbeat(&self, id: Uuid, attempt: i32) -> Result<bool, DbErr> {
+        let now = Utc::now();
+        let changes = ActiveModel {
+            locked_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        };
+
+        self.update_claimed(id, attempt, changes).await
+    }
+
+    async fn complete(&self, id: Uuid, attempt: i32) -> Result<bool, DbErr> {
+        let changes = ActiveModel {
+            status: Set(JobStatus::Succeeded),
+            locked_at: Set(None),
+            last_error: Set(None),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        };
+
+        self.update_claimed(id, attempt, changes).await
+    }
+
+    async fn fail(
+        &self,
+        id: Uuid,
+        attempt: i32,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbErr> {
+        let mut job = ActiveModel {
+            locked_at: Set(None),
+            last_error: Set(Some(error)),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        };
+
+        match retry_at {
+            Some(run_at) => {
+                job.status = Set(JobStatus::Pending);
+                job.run_at = Set(run_at.into());
+            }
+            None => job.status = Set(JobStatus::Failed),
+        }
+
+        self.update_claimed(id, attempt, job).await
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Model, DbErr> {
+        Entity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Job not found".to_string()))
+    }
+
+    async fn list(&self, status: Option<JobStatus>, limit: u64) -> Result<Vec<Model>, DbErr> {
+        let mut query = Entity::find();
+        if let Some(status) = status {
+            query = query.filter(Column::Status.eq(status));
+        }
+
+        query
+            .order_by_desc(Column::UpdatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn retry_failed(&self, id: Uuid) -> Result<Option<Model>, DbErr> {
+        let now = Utc::now();
+
+        // A single conditional update, so two retries cannot both requeue it.
+        let mut jobs = Entity::update_many()
+            .set(ActiveModel {
+                status: Set(JobStatus::Pending),
+                attempts: Set(0),
+                run_at: Set(now.into()),
+                locked_at: Set(None),
+                updated_at: Set(now.into()),
+                ..Default::default()
+            })
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Failed))
+            .exec_with_returning(&self.db)
+            .await?;
+
+        Ok(jobs.pop())
+    }
+
+    async fn purge_succeeded(&self, before: DateTime<Utc>) -> Result<u64, DbErr> {
+        let res = Entity::delete_many()
+            .filter(Column::Status.eq(JobStatus::Succeeded))
+            .filter(Column::UpdatedAt.lt(before))
+            .exec(&self.db)
+            .await?;
+
+        Ok(res.rows_affected)
+    }
+}
+
+impl JobRepositoryImpl {
+    /// Applies `changes` only if the job is still running `attempt`; a stale
+    /// worker must not overwrite the state of a newer claim.
+    async fn update_claimed(
+        &self,
+        id: Uuid,
+        attempt: i32,
+        changes: ActiveModel,
+    ) -> Result<bool, DbErr> {
+        let res = Entity::update_many()
+            .set(changes)
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(JobStatus::Running))
+            .filter(Column::Attempts.eq(attempt))
+            .exec(&self.db)
+            .await?;
+
+        Ok(res.rows_affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use serde_json::json;
+
+    const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn noop() -> NewJob {
+        NewJob {
+            name: "noop".to_string(),
+            payload: json!({}),
+            run_at: Utc::now(),
+            max_attempts: 1,
+            dedupe_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_requeues_only_failed_jobs() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = JobRepositoryImpl { db };
+        let job = repo.enqueue(noop()).await.unwrap().unwrap();
+
+        assert!(repo.retry_failed(job.id).await.unwrap().is_none());
+
+        let job = repo.claim_due(LOCK_TIMEOUT).await.unwrap().unwrap();
+        assert!(repo
+            .fail(job.id, job.attempts, "boom".to_string(), None)
+            .await
+            .unwrap());
+        let retried = repo.retry_failed(job.id).await.unwrap().unwrap();
+        assert_eq!(retried.status, JobStatus::Pending);
+        assert_eq!(retried.attempts, 0);
+
+        assert!(repo.retry_failed(job.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stale_worker_cannot_record_a_result() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = JobRepositoryImpl { db };
+        repo.enqueue(noop()).await.unwrap().unwrap();
+
+        let stale = repo.claim_due(LOCK_TIMEOUT).await.unwrap().unwrap();
+        assert!(repo.heartbeat(stale.id, stale.attempts).await.unwrap());
+
+        // The lock times out and a second worker claims the job again.
+        let reclaimed = repo.claim_due(Duration::ZERO).await.unwrap().unwrap();
+        assert_eq!(reclaimed.id, stale.id);
+
+        assert!(!repo.heartbeat(stale.id, stale.attempts).await.unwrap());
+        assert!(!repo
+            .fail(stale.id, stale.attempts, "late".to_string(), None)
+            .await
+            .unwrap());
+        assert!(repo
+            .complete(reclaimed.id, reclaimed.attempts)
+            .await
+            .unwrap());
+
+        let job = repo.get_by_id(stale.id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.last_error, None);
+    }
+}
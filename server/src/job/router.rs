@@ -0,0 +1,19 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::state::AppState,
+    job::controller,
+    middleware::auth::{is_admin, is_authenticated},
+};
+
+pub fn init() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::list))
+        .route("/{job_id}/retry", post(controller::retry))
+        .layer(middleware::from_fn(is_admin))
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,257 @@
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use cron::Schedule;
+use entity::job::Model;
+use serde_json::{json, Value};
+use tokio::task::JoinSet;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::config::Config;
+
+use super::handler::{Job, JobRegistry};
+use super::repository::{JobRepository, NewJob};
+
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+const MIN_HEARTBEAT: Duration = Duration::from_secs(1);
+
+/// A job enqueued on a cron schedule (six fields, seconds first).
+pub struct ScheduledJob {
+    pub name: &'static str,
+    pub schedule: Schedule,
+    pub payload: Value,
+}
+
+/// Runs queued jobs on a pool of workers and enqueues scheduled jobs when due.
+///
+/// On shutdown workers stop claiming new jobs but finish the one in flight.
+pub struct JobRunner<R: JobRepository + 'static> {
+    repo: Arc<R>,
+    registry: JobRegistry,
+    schedules: Vec<ScheduledJob>,
+    workers: usize,
+    poll_interval: Duration,
+    lock_timeout: Duration,
+    backoff_base: Duration,
+    max_attempts: i32,
+}
+
+impl<R: JobRepository + 'static> JobRunner<R> {
+    pub fn new(repo: Arc<R>, config: &Config) -> Self {
+        Self {
+            repo,
+            registry: JobRegistry::default(),
+            schedules: Vec::new(),
+            workers: config.job_workers,
+            poll_interval: Duration::from_millis(config.job_poll_interval_ms),
+            lock_timeout: Duration::from_secs(config.job_lock_timeout_secs),
+            backoff_base: Duration::from_secs(config.job_backoff_base_secs),
+            max_attempts: config.job_max_attempts,
+        }
+    }
+
+    pub fn register(mut self, job: impl Job + 'static) -> Self {
+        self.registry.register(job);
+        self
+    }
+
+    /// Registers `job` and enqueues it whenever `cron` fires. Panics on an
+    /// invalid expression, since schedules are fixed at startup.
+    pub fn schedule(mut self, job: impl Job + 'static, cron: &str) -> Self {
+        let name = job.name();
+        let schedule = Schedule::from_str(cron)
+            .unwrap_or_else(|err| panic!("Invalid cron expression for job `{name}`: {err}"));
+
+        self.registry.register(job);
+        self.schedules.push(ScheduledJob {
+            name,
+            schedule,
+            payload: json!({}),
+        });
+        self
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        let runner = Arc::new(self);
+        let mut tasks = JoinSet::new();
+
+        for worker_id in 0..runner.workers {
+            tasks.spawn(runner.clone().work(worker_id, shutdown.clone()));
+        }
+        for index in 0..runner.schedules.len() {
+            tasks.spawn(runner.clone().tick(index, shutdown.clone()));
+        }
+
+        while tasks.join_next().await.is_some() {}
+        info!("Job runner stopped");
+    }
+
+    async fn work(self: Arc<Self>, worker_id: usize, shutdown: CancellationToken) {
+        while !shutdown.is_cancelled() {
+            match self.repo.claim_due(self.lock_timeout).await {
+                Ok(Some(job)) => {
+                    self.execute(job).await;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => error!(worker_id, "Failed to claim job: {:?}", err),
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+
+    async fn execute(&self, job: Model) {
+        if job.attempts > job.max_attempts {
+            self.finish(
+                &job,
+                Err(anyhow::anyhow!("lock expired after the last attempt")),
+            )
+            .await;
+            return;
+        }
+
+        let result = match self.registry.get(&job.name) {
+            Some(handler) => {
+                debug!(job_id = %job.id, name = %job.name, attempt = job.attempts, "Running job");
+                self.with_heartbeat(&job, handler.run(job.payload.clone()))
+                    .await
+            }
+            None => Err(anyhow::anyhow!(
+                "no handler registered for job `{}`",
+                job.name
+            )),
+        };
+
+        self.finish(&job, result).await;
+    }
+
+    /// Renews the job's lock while `run` is in flight, so a slow job is not
+    /// mistaken for one whose worker died and claimed a second time.
+    async fn with_heartbeat(
+        &self,
+        job: &Model,
+        run: impl Future<Output = anyhow::Result<()>>,
+    ) -> anyhow::Result<()> {
+        tokio::pin!(run);
+        let mut heartbeat = time::interval((self.lock_timeout / 3).max(MIN_HEARTBEAT));
+        heartbeat.tick().await;
+
+        loop {
+            tokio::select! {
+                result = &mut run => return result,
+                _ = heartbeat.tick() => match self.repo.heartbeat(job.id, job.attempts).await {
+                    Ok(true) => {}
+                    Ok(false) => warn!(job_id = %job.id, "Job was claimed by another worker"),
+                    Err(err) => error!(job_id = %job.id, "Failed to renew job lock: {:?}", err),
+                },
+            }
+        }
+    }
+
+    async fn finish(&self, job: &Model, result: anyhow::Result<()>) {
+        let res = match result {
+            Ok(()) => self.repo.complete(job.id, job.attempts).await,
+            Err(err) => {
+                let retry_at = (job.attempts < job.max_attempts)
+                    .then(|| Utc::now() + backoff(self.backoff_base, job.attempts));
+
+                warn!(
+                    job_id = %job.id,
+                    name = %job.name,
+                    attempt = job.attempts,
+                    retry = retry_at.is_some(),
+                    "Job failed: {:?}",
+                    err
+                );
+                self.repo
+                    .fail(job.id, job.attempts, format!("{err:#}"), retry_at)
+                    .await
+            }
+        };
+
+        match res {
+            Ok(true) => {}
+            Ok(false) => warn!(
+                job_id = %job.id,
+                attempt = job.attempts,
+                "Dropped the result of a job claimed again by another worker"
+            ),
+            Err(err) => error!(job_id = %job.id, "Failed to record job result: {:?}", err),
+        }
+    }
+
+    async fn tick(self: Arc<Self>, index: usize, shutdown: CancellationToken) {
+        let scheduled = &self.schedules[index];
+
+        for fire_at in scheduled.schedule.upcoming(Utc) {
+            let wait = (fire_at - Utc::now()).to_std().unwrap_or_default();
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = time::sleep(wait) => {}
+            }
+
+            // Every instance runs the same schedules; the dedupe key makes
+            // sure each fire time is enqueued only once.
+            let job = NewJob {
+                name: scheduled.name.to_string(),
+                payload: scheduled.payload.clone(),
+                run_at: fire_at,
+                max_attempts: self.max_attempts,
+                dedupe_key: Some(format!("{}@{}", scheduled.name, fire_at.timestamp())),
+            };
+
+            if let Err(err) = self.repo.enqueue(job).await {
+                error!(
+                    name = scheduled.name,
+                    "Failed to enqueue scheduled job: {:?}", err
+                );
+            }
+        }
+    }
+}
+
+/// Exponential backoff: `base * 2^(attempt - 1)`, capped at one hour.
+pub fn backoff(base: Duration, attempt: i32) -> Duration {
+    let exponent = attempt.saturating_sub(1).clamp(0, 16) as u32;
+    base.saturating_mul(2u32.pow(exponent)).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_per_attempt() {
+        let base = Duration::from_secs(10);
+
+        assert_eq!(backoff(base, 1), Duration::from_secs(10));
+        assert_eq!(backoff(base, 2), Duration::from_secs(20));
+        assert_eq!(backoff(base, 3), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let base = Duration::from_secs(10);
+
+        assert_eq!(backoff(base, 20), MAX_BACKOFF);
+        assert_eq!(backoff(base, i32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_backoff_first_attempt_floor() {
+        let base = Duration::from_secs(10);
+
+        assert_eq!(backoff(base, 0), base);
+        assert_eq!(backoff(base, -1), base);
+    }
+}
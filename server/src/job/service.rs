@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use entity::job::Model;
+use entity::sea_orm_active_enums::JobStatus;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository::{JobRepository, NewJob};
+
+#[derive(Clone)]
+pub struct JobService<R: JobRepository> {
+    pub repo: Arc<R>,
+    pub max_attempts: i32,
+}
+
+impl<R: JobRepository> JobService<R> {
+    pub fn new(repo: Arc<R>, max_attempts: i32) -> Self {
+        JobService { repo, max_attempts }
+    }
+
+    /// Queues `name` to run at `run_at`; the job is picked up by the next free worker.
+    pub async fn enqueue(
+        &self,
+        name: &str,
+        payload: Value,
+        run_at: DateTime<Utc>,
+    ) -> ServiceResult<Model> {
+        let job = NewJob {
+            name: name.to_string(),
+            payload,
+            run_at,
+            max_attempts: self.max_attempts,
+            dedupe_key: None,
+        };
+
+        self.repo
+            .enqueue(job)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::AlreadyExists)
+    }
+
+    pub async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        limit: u64,
+    ) -> ServiceResult<Vec<Model>> {
+        self.repo
+            .list(status, limit)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn retry_job(&self, id: Uuid) -> ServiceResult<Model> {
+        match self
+            .repo
+            .retry_failed(id)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            Some(job) => Ok(job),
+            None => {
+                // Tell a missing job apart from one that is not failed.
+                self.repo.get_by_id(id).await.map_err(ServiceError::from)?;
+                Err(ServiceError::Conflict("only failed jobs can be retried"))
+            }
+        }
+    }
+}
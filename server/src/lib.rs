@@ -1,30 +1,154 @@
+pub mod calendar {
+    pub mod controller;
+    pub mod ics;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+}
 pub mod config;
 pub mod database;
+pub mod grpc {
+    pub mod auth;
+    pub mod error;
+    pub mod model;
+    pub mod proto;
+    pub mod server;
+    pub mod service;
+}
+pub mod graphql {
+    pub mod controller;
+    pub mod error;
+    pub mod loader;
+    pub mod model;
+    pub mod router;
+    pub mod schema;
+}
+pub mod job {
+    pub mod controller;
+    pub mod handler;
+    pub mod housekeeping;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod runner;
+    pub mod service;
+}
+pub mod mail {
+    pub mod controller;
+    pub mod mailer;
+    pub mod model;
+    pub mod reminder;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+    pub mod template;
+}
 pub mod middleware {
     pub mod auth;
+    pub mod deprecation;
+}
+pub mod notification {
+    pub mod controller;
+    pub mod model;
+    pub mod overdue;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+}
+pub mod openapi {
+    pub mod controller;
+    pub mod router;
+    pub mod spec;
+}
+pub mod outbox {
+    pub mod controller;
+    pub mod model;
+    pub mod relay;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+    pub mod sink;
 }
 pub mod router;
+pub mod saved_filter {
+    pub mod controller;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+}
 pub mod server;
+pub mod share {
+    pub mod controller;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+}
+pub mod time_entry {
+    pub mod controller;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+}
 pub mod todo {
+    pub mod archive;
     pub mod controller;
+    pub mod filter;
+    pub mod markdown;
     pub mod model;
+    pub mod path;
+    pub mod quick_add;
     pub mod repository;
     pub mod router;
     pub mod service;
+    pub mod status;
+    pub mod v2 {
+        pub mod controller;
+        pub mod model;
+        pub mod router;
+    }
+    pub mod view;
+}
+pub mod todo_template {
+    pub mod controller;
+    pub mod model;
+    pub mod repository;
+    pub mod router;
+    pub mod service;
+    pub mod substitute;
 }
 pub mod common {
+    pub mod envelope;
     pub mod error;
     pub mod fetch;
     pub mod jwt;
+    pub mod ndjson;
+    pub mod negotiate;
+    pub mod pagination;
+    pub mod serialization;
     pub mod state;
-    pub mod validated_json;
+    #[cfg(test)]
+    pub mod test_db;
+    pub mod token;
+    pub mod validated_body;
     pub mod validated_path;
+    pub mod validated_query;
 }
 
 use std::error::Error;
+use std::sync::Arc;
 
 use common::state::AppState;
 use config::CONFIG;
+use job::{housekeeping::PurgeSucceededJobs, repository::JobRepositoryImpl, runner::JobRunner};
+use mail::reminder::SendDueReminders;
+use notification::overdue::NotifyOverdueTodos;
+use outbox::relay::OutboxRelay;
+use todo::archive::ArchiveCompletedTodos;
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
@@ -35,28 +159,89 @@ pub async fn start() -> Result<(), Box<dyn Error>> {
         .init();
 
     let db = database::create(&CONFIG).await;
-    let app_state = AppState::new(db);
+    let app_state = AppState::new(db.clone(), &CONFIG);
+    let shutdown = CancellationToken::new();
 
-    let router = router::init()
-        .with_state(app_state)
-        .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()));
-    let server = server::create(router).await?;
-    let signal = tokio::signal::ctrl_c();
+    let relay = OutboxRelay::new(db.clone(), &CONFIG, app_state.event_bus.clone());
+    let relay_task = tokio::spawn(relay.run(shutdown.clone()));
 
-    tokio::select! {
-        res = server => {
-            if let Err(err) = res {
-                tracing::error!("Server error: {}", err);
-            }
-        }
-        _ = signal => {
-            tracing::info!("Shutdown signal received");
+    let mailer = mail::mailer::from_config(&CONFIG)?;
+    let reminders = SendDueReminders::new(db.clone(), mailer, CONFIG.reminder_batch_size);
+
+    let job_repo = Arc::new(JobRepositoryImpl { db });
+    let jobs = JobRunner::new(job_repo.clone(), &CONFIG)
+        .schedule(
+            PurgeSucceededJobs::new(job_repo, CONFIG.job_retention_days),
+            "0 0 3 * * *",
+        )
+        .schedule(
+            ArchiveCompletedTodos::new(
+                app_state.todo_service.clone(),
+                CONFIG.todo_archive_after_days,
+                CONFIG.todo_archive_batch_size,
+            ),
+            "0 0 * * * *",
+        )
+        .schedule(
+            NotifyOverdueTodos::new(
+                app_state.notification_service.clone(),
+                CONFIG.notification_overdue_lookback_hours,
+            ),
+            "0 */5 * * * *",
+        )
+        .schedule(reminders, "0 * * * * *");
+    let jobs_task = tokio::spawn(jobs.run(shutdown.clone()));
+
+    // gRPC shares the HTTP listener unless it is given a port of its own.
+    let grpc_routes = grpc::server::routes(app_state.clone());
+    let mut router = router::init().with_state(app_state);
+    let mut grpc_task = None;
+    match CONFIG.grpc_port {
+        Some(port) => {
+            let grpc_server =
+                grpc::server::create(grpc_routes.layer(TraceLayer::new_for_grpc()), port)
+                    .await?
+                    .with_graceful_shutdown(shutdown.clone().cancelled_owned());
+            grpc_task = Some(tokio::spawn(async move {
+                if let Err(err) = grpc_server.await {
+                    tracing::error!("gRPC server error: {}", err);
+                }
+            }));
         }
+        None => router = router.merge(grpc_routes),
+    }
+
+    let router = router.layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()));
+    let server = server::create(router).await?;
+
+    tokio::spawn(shutdown_on_signal(shutdown.clone()));
+
+    if let Err(err) = server
+        .with_graceful_shutdown(shutdown.clone().cancelled_owned())
+        .await
+    {
+        tracing::error!("Server error: {}", err);
+    }
+
+    // Background tasks finish their in-flight work before the process exits.
+    shutdown.cancel();
+    let _ = tokio::join!(relay_task, jobs_task);
+    if let Some(grpc_task) = grpc_task {
+        let _ = grpc_task.await;
     }
 
     Ok(())
 }
 
+async fn shutdown_on_signal(shutdown: CancellationToken) {
+    if let Err(err) = tokio::signal::ctrl_c().await {
+        tracing::error!("Failed to listen for shutdown signal: {}", err);
+    }
+
+    tracing::info!("Shutdown signal received");
+    shutdown.cancel();
+}
+
 pub fn main() {
     if let Err(err) = start() {
         eprintln!("Error: {err}");
@@ -0,0 +1,58 @@
+use axum::{extract::State, response::NoContent, Extension};
+use tracing::error;
+
+use crate::{
+    common::error::ApiResult, common::jwt::Claims, common::negotiate::Negotiated,
+    common::state::AppState, common::validated_body::ValidatedBody,
+};
+
+use super::model::{EmailAddressResponse, SetEmailAddressRequest};
+
+pub async fn get_address(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<EmailAddressResponse>> {
+    let address = state
+        .email_service
+        .get_address(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to get email address: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(EmailAddressResponse::from(address)))
+}
+
+pub async fn set_address(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<SetEmailAddressRequest>,
+) -> ApiResult<Negotiated<EmailAddressResponse>> {
+    let address = state
+        .email_service
+        .set_address(&claims.sub, payload.email)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to set email address: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(EmailAddressResponse::from(address)))
+}
+
+pub async fn delete_address(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<NoContent> {
+    state
+        .email_service
+        .delete_address(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to delete email address: {:?}", err);
+            err
+        })?;
+
+    Ok(NoContent)
+}
@@ -0,0 +1,240 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::info;
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailTransportKind {
+    Smtp,
+    File,
+    Log,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+#[derive(Debug, Error)]
+pub enum MailError {
+    /// The message can never be delivered, e.g. an invalid or refused address.
+    #[error("message rejected: {0}")]
+    Rejected(String),
+    /// The transport failed; sending again later may succeed.
+    #[error("transport unavailable: {0}")]
+    Unavailable(String),
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: EmailMessage) -> Result<(), MailError>;
+}
+
+/// Builds the transport selected by `MAIL_TRANSPORT`.
+pub fn from_config(config: &Config) -> anyhow::Result<Arc<dyn Mailer>> {
+    let from: Mailbox = config.mail_from.parse()?;
+
+    Ok(match config.mail_transport {
+        MailTransportKind::Smtp => Arc::new(SmtpMailer::new(config, from)?),
+        MailTransportKind::File => Arc::new(FileMailer::new(config.mail_file_dir.clone(), from)),
+        MailTransportKind::Log => Arc::new(LogMailer),
+    })
+}
+
+fn build_message(from: &Mailbox, message: EmailMessage) -> Result<Message, MailError> {
+    let to: Mailbox = message
+        .to
+        .parse()
+        .map_err(|err| MailError::Rejected(format!("invalid recipient: {err}")))?;
+
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(message.subject)
+        .multipart(MultiPart::alternative_plain_html(
+            message.text,
+            message.html,
+        ))
+        .map_err(|err| MailError::Rejected(err.to_string()))
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// Uses STARTTLS unless `SMTP_TLS=false`, which suits a local SMTP sink.
+    pub fn new(config: &Config, from: Mailbox) -> anyhow::Result<Self> {
+        let mut builder = if config.smtp_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+        }
+        .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), MailError> {
+        let message = build_message(&self.from, message)?;
+
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                if err.is_permanent() {
+                    MailError::Rejected(err.to_string())
+                } else {
+                    MailError::Unavailable(err.to_string())
+                }
+            })
+    }
+}
+
+/// Writes each message as an `.eml` file, for development.
+pub struct FileMailer {
+    transport: AsyncFileTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self {
+            transport: AsyncFileTransport::new(dir.into()),
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), MailError> {
+        let message = build_message(&self.from, message)?;
+
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|err| MailError::Unavailable(err.to_string()))
+    }
+}
+
+/// Logs the plain-text body instead of sending anything.
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), MailError> {
+        info!(to = %message.to, subject = %message.subject, "{}", message.text);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn message(to: &str) -> EmailMessage {
+        EmailMessage {
+            to: to.to_string(),
+            subject: "Reminder: Pay rent".to_string(),
+            text: "Pay rent".to_string(),
+            html: "<p>Pay rent</p>".to_string(),
+        }
+    }
+
+    /// Accepts a single SMTP session and returns the received DATA section.
+    async fn smtp_sink(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut data = String::new();
+        let mut in_data = false;
+
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n").await.unwrap();
+                } else {
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                continue;
+            }
+
+            let reply: &[u8] = match line.get(..4).unwrap_or_default().to_uppercase().as_str() {
+                "EHLO" => b"250 localhost\r\n",
+                "DATA" => {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                }
+                "QUIT" => {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                }
+                _ => b"250 ok\r\n",
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+
+        data
+    }
+
+    #[tokio::test]
+    async fn test_smtp_mailer_delivers_to_local_sink() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sink = tokio::spawn(smtp_sink(listener));
+
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous("127.0.0.1")
+            .port(port)
+            .build();
+        let mailer = SmtpMailer {
+            transport,
+            from: "Todos <noreply@example.com>".parse().unwrap(),
+        };
+
+        mailer.send(message("alice@example.com")).await.unwrap();
+        drop(mailer);
+        let data = sink.await.unwrap();
+
+        assert!(data.contains("Subject: Reminder: Pay rent"));
+        assert!(data.contains("To: alice@example.com"));
+        assert!(data.contains("multipart/alternative"));
+    }
+
+    #[test]
+    fn test_invalid_recipient_is_rejected() {
+        let from: Mailbox = "noreply@example.com".parse().unwrap();
+        let result = build_message(&from, message("not an address"));
+        assert!(matches!(result, Err(MailError::Rejected(_))));
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, FixedOffset};
+use entity::user_email::Model;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::common::serialization::timestamp;
+
+#[derive(Serialize)]
+pub struct EmailAddressResponse {
+    pub email: String,
+    #[serde(serialize_with = "timestamp")]
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for EmailAddressResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            email: model.email,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetEmailAddressRequest {
+    #[validate(email, length(max = 254))]
+    pub email: String,
+}
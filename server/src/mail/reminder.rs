@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use entity::reminder_delivery::Model as DeliveryModel;
+use entity::sea_orm_active_enums::TodoStatus;
+use entity::todo::{Entity as TodoEntity, Model as TodoModel};
+use sea_orm::{DatabaseConnection, EntityTrait, TransactionTrait};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::job::handler::Job;
+
+use super::mailer::{MailError, Mailer};
+use super::{repository, template};
+
+/// How long a claimed delivery is left alone before another run retries it.
+const CLAIM_LEASE_SECS: i64 = 300;
+
+/// Emails due `remind_at` reminders to the owner and assignee of each todo.
+///
+/// A due reminder is first expanded into one delivery per recipient and
+/// marked as handled. Each delivery is then claimed and committed before its
+/// email is sent and marked sent afterwards, so no lock is held while mailing
+/// and a failed send is retried for that recipient alone. Only a crash
+/// between sending and marking can repeat an email, and only to one
+/// recipient.
+pub struct SendDueReminders {
+    db: DatabaseConnection,
+    mailer: Arc<dyn Mailer>,
+    batch_size: u64,
+}
+
+impl SendDueReminders {
+    pub fn new(db: DatabaseConnection, mailer: Arc<dyn Mailer>, batch_size: u64) -> Self {
+        Self {
+            db,
+            mailer,
+            batch_size,
+        }
+    }
+
+    /// Sends one pending delivery, or expands the next due reminder into
+    /// deliveries; returns `false` once neither is left.
+    async fn send_next(&self) -> anyhow::Result<bool> {
+        let now = Utc::now();
+        let lease = Duration::seconds(CLAIM_LEASE_SECS);
+
+        if let Some(delivery) = repository::claim_next_delivery(&self.db, now, lease).await? {
+            self.deliver(&delivery).await?;
+            return Ok(true);
+        }
+
+        let txn = self.db.begin().await?;
+        let Some(todo) = repository::lock_next_due_reminder(&txn, now).await? else {
+            return Ok(false);
+        };
+
+        // Finished todos need no reminder; they are only marked as handled.
+        if is_open(&todo) {
+            repository::insert_deliveries(&txn, &todo, recipients(&todo)).await?;
+        }
+        repository::mark_reminded(&txn, todo.id, now).await?;
+        txn.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn deliver(&self, delivery: &DeliveryModel) -> anyhow::Result<()> {
+        // The todo may have been finished or its reminder moved since.
+        let todo = TodoEntity::find_by_id(delivery.todo_id)
+            .one(&self.db)
+            .await?
+            .filter(|todo| is_open(todo) && todo.remind_at == Some(delivery.remind_at));
+        let address = match todo {
+            Some(_) => repository::address_of(&self.db, &delivery.user_id).await?,
+            None => None,
+        };
+
+        if let (Some(todo), Some(address)) = (todo, address) {
+            let message = template::reminder(&todo, &address)?;
+            match self.mailer.send(message).await {
+                Ok(()) => {}
+                // Retrying cannot help, so the reminder still counts as sent.
+                Err(MailError::Rejected(reason)) => {
+                    warn!(todo_id = %todo.id, %reason, "Reminder email rejected");
+                }
+                // Left claimed, so it is retried once the lease runs out.
+                Err(err @ MailError::Unavailable(_)) => return Err(err.into()),
+            }
+        }
+
+        repository::mark_sent(&self.db, delivery, Utc::now()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Job for SendDueReminders {
+    fn name(&self) -> &'static str {
+        "reminders.send_due"
+    }
+
+    async fn run(&self, _payload: Value) -> anyhow::Result<()> {
+        let mut handled = 0;
+        while handled < self.batch_size && self.send_next().await? {
+            handled += 1;
+        }
+
+        info!(handled, "Handled due reminders");
+        Ok(())
+    }
+}
+
+fn is_open(todo: &TodoModel) -> bool {
+    todo.archived_at.is_none() && !matches!(todo.status, TodoStatus::Done | TodoStatus::Cancelled)
+}
+
+fn recipients(todo: &TodoModel) -> Vec<String> {
+    let mut user_ids: Vec<String> = todo.owner_id.iter().cloned().collect();
+    if let Some(assignee_id) = &todo.assignee_id {
+        if !user_ids.contains(assignee_id) {
+            user_ids.push(assignee_id.clone());
+        }
+    }
+    user_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use entity::reminder_delivery::{Column as DeliveryColumn, Entity as DeliveryEntity};
+    use sea_orm::sea_query::Expr;
+    use sea_orm::{ColumnTrait, QueryFilter};
+
+    use super::*;
+    use crate::common::test_db;
+    use crate::mail::mailer::EmailMessage;
+    use crate::mail::repository::{EmailRepository, EmailRepositoryImpl};
+    use crate::todo::repository::{NewTodo, TodoRepository, TodoRepositoryImpl};
+
+    /// Records every attempt and fails the first one to `flaky`.
+    struct FlakyMailer {
+        flaky: String,
+        attempts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Mailer for FlakyMailer {
+        async fn send(&self, message: EmailMessage) -> Result<(), MailError> {
+            let mut attempts = self.attempts.lock().unwrap();
+            let first = !attempts.contains(&message.to);
+            attempts.push(message.to.clone());
+            if first && message.to == self.flaky {
+                return Err(MailError::Unavailable("connection refused".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_recipient_is_retried_alone() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let emails = EmailRepositoryImpl { db: db.clone() };
+        emails
+            .save("alice", "alice@example.com".into())
+            .await
+            .unwrap();
+        emails.save("bob", "bob@example.com".into()).await.unwrap();
+        let todo = TodoRepositoryImpl { db: db.clone() }
+            .create(NewTodo {
+                title: "Pay rent".to_string(),
+                owner_id: Some("alice".to_string()),
+                remind_at: Some(Utc::now() - Duration::minutes(1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        TodoEntity::update_many()
+            .col_expr(entity::todo::Column::AssigneeId, Expr::value("bob"))
+            .filter(entity::todo::Column::Id.eq(todo.id))
+            .exec(&db)
+            .await
+            .unwrap();
+        let mailer = Arc::new(FlakyMailer {
+            flaky: "bob@example.com".to_string(),
+            attempts: Mutex::new(Vec::new()),
+        });
+        let job = SendDueReminders::new(db.clone(), mailer.clone(), 10);
+
+        assert!(job.run(Value::Null).await.is_err());
+
+        // Expire bob's claim instead of waiting for the lease.
+        DeliveryEntity::update_many()
+            .col_expr(DeliveryColumn::ClaimedUntil, Expr::value(Utc::now()))
+            .filter(DeliveryColumn::SentAt.is_null())
+            .exec(&db)
+            .await
+            .unwrap();
+        job.run(Value::Null).await.unwrap();
+        job.run(Value::Null).await.unwrap();
+
+        let mut attempts = mailer.attempts.lock().unwrap().clone();
+        attempts.sort();
+        assert_eq!(
+            attempts,
+            ["alice@example.com", "bob@example.com", "bob@example.com"]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::reminder_delivery::{
+    ActiveModel as DeliveryActiveModel, Column as DeliveryColumn, Entity as DeliveryEntity,
+    Model as DeliveryModel,
+};
+use entity::todo::{Column as TodoColumn, Entity as TodoEntity, Model as TodoModel};
+use entity::user_email::{ActiveModel, Column, Entity, Model};
+use sea_orm::sea_query::{Expr, LockBehavior, LockType, OnConflict};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+
+#[async_trait]
+pub trait EmailRepository: Send + Sync {
+    async fn get(&self, user_id: &str) -> Result<Model, DbErr>;
+    async fn save(&self, user_id: &str, email: String) -> Result<Model, DbErr>;
+    async fn delete(&self, user_id: &str) -> Result<(), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct EmailRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl EmailRepository for EmailRepositoryImpl {
+    async fn get(&self, user_id: &str) -> Result<Model, DbErr> {
+        Entity::find_by_id(user_id.to_string())
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Email address not found".to_string()))
+    }
+
+    async fn save(&self, user_id: &str, email: String) -> Result<Model, DbErr> {
+        Entity::insert(ActiveModel {
+            user_id: Set(user_id.to_string()),
+            email: Set(email),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::column(Column::UserId)
+                .update_column(Column::Email)
+                .value(Column::UpdatedAt, Utc::now())
+                .to_owned(),
+        )
+        .exec_with_returning(&self.db)
+        .await
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<(), DbErr> {
+        let res = Entity::delete_by_id(user_id.to_string())
+            .exec(&self.db)
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Email address not found".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Locks the earliest unsent reminder due at `now`. Must be called inside a
+/// transaction; reminders locked by another instance are skipped.
+pub async fn lock_next_due_reminder<C: ConnectionTrait>(
+    conn: &C,
+    now: DateTime<Utc>,
+) -> Result<Option<TodoModel>, DbErr> {
+    TodoEntity::find()
+        .filter(TodoColumn::RemindAt.lte(now))
+        .filter(TodoColumn::RemindedAt.is_null())
+        .order_by_asc(TodoColumn::RemindAt)
+        .limit(1)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .one(conn)
+        .await
+}
+
+pub async fn mark_reminded<C: ConnectionTrait>(
+    conn: &C,
+    todo_id: uuid::Uuid,
+    at: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    TodoEntity::update_many()
+        .col_expr(TodoColumn::RemindedAt, at.into())
+        .filter(TodoColumn::Id.eq(todo_id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Records one pending delivery per recipient of the todo's current reminder.
+/// Recipients already recorded for it are left alone.
+pub async fn insert_deliveries<C: ConnectionTrait>(
+    conn: &C,
+    todo: &TodoModel,
+    user_ids: Vec<String>,
+) -> Result<(), DbErr> {
+    let Some(remind_at) = todo.remind_at else {
+        return Ok(());
+    };
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    DeliveryEntity::insert_many(user_ids.into_iter().map(|user_id| DeliveryActiveModel {
+        todo_id: Set(todo.id),
+        user_id: Set(user_id),
+        remind_at: Set(remind_at),
+        ..Default::default()
+    }))
+    .on_conflict(
+        OnConflict::columns([
+            DeliveryColumn::TodoId,
+            DeliveryColumn::UserId,
+            DeliveryColumn::RemindAt,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Claims the oldest unsent delivery for `lease` and commits, so the email is
+/// sent without holding row locks. An instance that dies before marking it
+/// sent leaves it to be claimed again once the lease runs out.
+pub async fn claim_next_delivery<C: TransactionTrait>(
+    db: &C,
+    now: DateTime<Utc>,
+    lease: chrono::Duration,
+) -> Result<Option<DeliveryModel>, DbErr> {
+    let txn = db.begin().await?;
+
+    let delivery = DeliveryEntity::find()
+        .filter(DeliveryColumn::SentAt.is_null())
+        .filter(DeliveryColumn::ClaimedUntil.lte(now))
+        .order_by_asc(DeliveryColumn::RemindAt)
+        .limit(1)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .one(&txn)
+        .await?;
+
+    if let Some(delivery) = &delivery {
+        DeliveryEntity::update_many()
+            .col_expr(DeliveryColumn::ClaimedUntil, Expr::value(now + lease))
+            .filter(DeliveryColumn::TodoId.eq(delivery.todo_id))
+            .filter(DeliveryColumn::UserId.eq(delivery.user_id.as_str()))
+            .filter(DeliveryColumn::RemindAt.eq(delivery.remind_at))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+    Ok(delivery)
+}
+
+/// Marks a delivery as done, whether it was sent or dropped.
+pub async fn mark_sent<C: ConnectionTrait>(
+    conn: &C,
+    delivery: &DeliveryModel,
+    at: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    DeliveryEntity::update_many()
+        .col_expr(DeliveryColumn::SentAt, Expr::value(at))
+        .filter(DeliveryColumn::TodoId.eq(delivery.todo_id))
+        .filter(DeliveryColumn::UserId.eq(delivery.user_id.as_str()))
+        .filter(DeliveryColumn::RemindAt.eq(delivery.remind_at))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// The registered address of a user, if any.
+pub async fn address_of<C: ConnectionTrait>(
+    conn: &C,
+    user_id: &str,
+) -> Result<Option<String>, DbErr> {
+    Ok(Entity::find_by_id(user_id.to_string())
+        .one(conn)
+        .await?
+        .map(|address| address.email))
+}
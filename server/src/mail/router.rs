@@ -0,0 +1,15 @@
+use axum::{middleware, routing::get, Router};
+
+use crate::{common::state::AppState, mail::controller, middleware::auth::is_authenticated};
+
+pub fn init() -> Router<AppState> {
+    // The address reminders are sent to, one per user.
+    Router::new()
+        .route(
+            "/",
+            get(controller::get_address)
+                .put(controller::set_address)
+                .delete(controller::delete_address),
+        )
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use entity::user_email::Model;
+
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository::EmailRepository;
+
+#[derive(Clone)]
+pub struct EmailService<R: EmailRepository> {
+    pub repo: Arc<R>,
+}
+
+impl<R: EmailRepository> EmailService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        EmailService { repo }
+    }
+
+    pub async fn get_address(&self, user_id: &str) -> ServiceResult<Model> {
+        self.repo.get(user_id).await.map_err(ServiceError::from)
+    }
+
+    pub async fn set_address(&self, user_id: &str, email: String) -> ServiceResult<Model> {
+        self.repo
+            .save(user_id, email)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn delete_address(&self, user_id: &str) -> ServiceResult<()> {
+        self.repo.delete(user_id).await.map_err(ServiceError::from)
+    }
+}
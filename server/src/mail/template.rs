@@ -0,0 +1,102 @@
+use askama::Template;
+use entity::todo::Model as TodoModel;
+
+use super::mailer::EmailMessage;
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[derive(Template)]
+#[template(path = "email/reminder.txt")]
+struct ReminderText<'a> {
+    title: &'a str,
+    due_at: Option<String>,
+    remind_at: String,
+}
+
+#[derive(Template)]
+#[template(path = "email/reminder.html")]
+struct ReminderHtml<'a> {
+    title: &'a str,
+    due_at: Option<String>,
+    remind_at: String,
+}
+
+/// Renders the plain-text and HTML bodies of a todo reminder.
+pub fn reminder(todo: &TodoModel, to: &str) -> Result<EmailMessage, askama::Error> {
+    let format =
+        |at: chrono::DateTime<chrono::FixedOffset>| at.to_utc().format(DATE_FORMAT).to_string();
+    let due_at = todo.due_at.map(format);
+    let remind_at = todo.remind_at.map(format).unwrap_or_default();
+
+    Ok(EmailMessage {
+        to: to.to_string(),
+        subject: format!("Reminder: {}", todo.title),
+        text: ReminderText {
+            title: &todo.title,
+            due_at: due_at.clone(),
+            remind_at: remind_at.clone(),
+        }
+        .render()?,
+        html: ReminderHtml {
+            title: &todo.title,
+            due_at,
+            remind_at,
+        }
+        .render()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use entity::sea_orm_active_enums::TodoStatus;
+    use uuid::Uuid;
+
+    fn todo(title: &str) -> TodoModel {
+        let now = Utc::now().fixed_offset();
+        let at = DateTime::parse_from_rfc3339("2024-03-06T17:00:00Z").unwrap();
+        TodoModel {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            created_at: now,
+            updated_at: now,
+            change_seq: 1,
+            completed_at: None,
+            archived_at: None,
+            status: TodoStatus::Todo,
+            project_id: None,
+            owner_id: Some("alice".to_string()),
+            due_at: Some(at),
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: None,
+            remind_at: Some(at),
+            reminded_at: None,
+            parent_id: None,
+            position: None,
+            description: None,
+            number: 1,
+        }
+    }
+
+    #[test]
+    fn test_reminder_renders_both_bodies() {
+        let email = reminder(&todo("Pay rent"), "alice@example.com").unwrap();
+
+        assert_eq!(email.subject, "Reminder: Pay rent");
+        assert!(email.text.contains("Reminder: Pay rent"));
+        assert!(email.text.contains("Due: 2024-03-06 17:00 UTC"));
+        assert!(email.html.contains("<h1>Reminder: Pay rent</h1>"));
+    }
+
+    #[test]
+    fn test_reminder_escapes_html_only() {
+        let email = reminder(&todo("<b>Rent</b> & bills"), "alice@example.com").unwrap();
+
+        assert!(email.text.contains("<b>Rent</b> & bills"));
+        assert!(email
+            .html
+            .contains("&#60;b&#62;Rent&#60;/b&#62; &#38; bills"));
+    }
+}
@@ -1,7 +1,7 @@
 use axum::{extract::Request, middleware::Next, response::Response};
 use reqwest::header;
 
-use crate::{common::error::ApiError, common::jwt};
+use crate::{common::error::ApiError, common::jwt, common::jwt::Claims, config::CONFIG};
 
 pub async fn is_authenticated(mut req: Request, next: Next) -> Result<Response, ApiError> {
     let token = extract_token(&req)?;
@@ -11,6 +11,21 @@ pub async fn is_authenticated(mut req: Request, next: Next) -> Result<Response,
     Ok(next.run(req).await)
 }
 
+/// Must be layered inside [`is_authenticated`], which supplies the claims.
+pub async fn is_admin(req: Request, next: Next) -> Result<Response, ApiError> {
+    ensure_admin(req.extensions().get::<Claims>(), &CONFIG.admin_subjects)?;
+    Ok(next.run(req).await)
+}
+
+fn ensure_admin(claims: Option<&Claims>, admins: &[String]) -> Result<(), ApiError> {
+    let claims = claims.ok_or(ApiError::Unauthorized("Missing token"))?;
+    if admins.contains(&claims.sub) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden("admin access required"))
+    }
+}
+
 fn extract_token(req: &Request) -> Result<&str, ApiError> {
     req.headers()
         .get(header::AUTHORIZATION)
@@ -129,4 +144,23 @@ mod tests {
             Err(ApiError::Unauthorized("Missing token"))
         ));
     }
+
+    #[test]
+    fn test_ensure_admin_checks_subject() {
+        let admins = vec!["admin-1".to_string()];
+        let claims = |sub: &str| Claims {
+            sub: sub.to_string(),
+            exp: 0,
+        };
+
+        assert!(ensure_admin(Some(&claims("admin-1")), &admins).is_ok());
+        assert!(matches!(
+            ensure_admin(Some(&claims("user-1")), &admins),
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(matches!(
+            ensure_admin(None, &admins),
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
 }
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+
+use crate::config::Config;
+
+/// When an API version was deprecated and when it will be removed, announced
+/// on its responses through `Deprecation` (RFC 9745) and `Sunset` (RFC 8594).
+#[derive(Debug, Clone, Default)]
+pub struct DeprecationPolicy {
+    pub deprecated_at: Option<DateTime<Utc>>,
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+impl DeprecationPolicy {
+    pub fn v1(config: &Config) -> Self {
+        Self {
+            deprecated_at: config.api_v1_deprecated_at,
+            sunset_at: config.api_v1_sunset_at,
+        }
+    }
+
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(at) = self.deprecated_at {
+            insert(&mut headers, "deprecation", format!("@{}", at.timestamp()));
+        }
+        if let Some(at) = self.sunset_at {
+            let date = at.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            insert(&mut headers, "sunset", date);
+        }
+        headers
+    }
+}
+
+fn insert(headers: &mut HeaderMap, name: &'static str, value: String) {
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        headers.insert(HeaderName::from_static(name), value);
+    }
+}
+
+pub async fn deprecation_headers(
+    State(policy): State<DeprecationPolicy>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(req).await;
+    response.headers_mut().extend(policy.headers());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers_follow_the_rfc_formats() {
+        let policy = DeprecationPolicy {
+            deprecated_at: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+            sunset_at: Some("2025-06-30T23:59:59Z".parse().unwrap()),
+        };
+
+        let headers = policy.headers();
+
+        assert_eq!(headers["deprecation"], "@1735689600");
+        assert_eq!(headers["sunset"], "Mon, 30 Jun 2025 23:59:59 GMT");
+    }
+
+    #[test]
+    fn test_unconfigured_policy_adds_nothing() {
+        assert!(DeprecationPolicy::default().headers().is_empty());
+    }
+}
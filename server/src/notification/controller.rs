@@ -0,0 +1,84 @@
+use axum::{extract::State, Extension};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    common::error::ApiResult, common::jwt::Claims, common::negotiate::Negotiated,
+    common::state::AppState, common::validated_path::ValidatedPath,
+    common::validated_query::ValidatedQuery,
+};
+
+use super::model::{
+    MarkAllReadResponse, NotificationListQuery, NotificationResponse, UnreadCountResponse,
+};
+
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedQuery(query): ValidatedQuery<NotificationListQuery>,
+) -> ApiResult<Negotiated<Vec<NotificationResponse>>> {
+    let notifications = state
+        .notification_service
+        .list_notifications(&claims.sub, query.unread, query.limit)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to list notifications: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        notifications
+            .into_iter()
+            .map(NotificationResponse::from)
+            .collect(),
+    ))
+}
+
+pub async fn unread_count(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<UnreadCountResponse>> {
+    let unread = state
+        .notification_service
+        .unread_count(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to count unread notifications: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(UnreadCountResponse { unread }))
+}
+
+pub async fn mark_read(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(notification_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<NotificationResponse>> {
+    let notification = state
+        .notification_service
+        .mark_read(&claims.sub, notification_id)
+        .await
+        .map_err(|err| {
+            error!(notification_id = %notification_id, "Failed to mark notification read: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(NotificationResponse::from(notification)))
+}
+
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<MarkAllReadResponse>> {
+    let updated = state
+        .notification_service
+        .mark_all_read(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to mark notifications read: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(MarkAllReadResponse { updated }))
+}
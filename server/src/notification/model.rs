@@ -0,0 +1,212 @@
+use chrono::{DateTime, FixedOffset};
+use entity::notification::Model;
+use entity::sea_orm_active_enums::NotificationKind;
+use entity::todo::Model as TodoModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::common::serialization::timestamp;
+
+/// A notification to be written alongside the change that caused it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewNotification {
+    pub user_id: String,
+    pub kind: NotificationKind,
+    pub todo_id: Uuid,
+    pub actor_id: Option<String>,
+    pub message: String,
+    pub dedupe_key: Option<String>,
+}
+
+impl NewNotification {
+    /// Notifies the new assignee, unless they assigned themselves.
+    pub fn assigned(todo: &TodoModel, actor_id: Option<&str>) -> Option<Self> {
+        let assignee_id = todo.assignee_id.as_deref()?;
+        if actor_id == Some(assignee_id) {
+            return None;
+        }
+
+        Some(Self {
+            user_id: assignee_id.to_string(),
+            kind: NotificationKind::Assigned,
+            todo_id: todo.id,
+            actor_id: actor_id.map(String::from),
+            message: format!("You were assigned \"{}\"", todo.title),
+            dedupe_key: None,
+        })
+    }
+
+    /// Notifies the owner of a commented todo, unless they wrote the comment.
+    pub fn commented(todo: &TodoModel, author_id: &str) -> Option<Self> {
+        let owner_id = todo.owner_id.as_deref()?;
+        if owner_id == author_id {
+            return None;
+        }
+
+        Some(Self {
+            user_id: owner_id.to_string(),
+            kind: NotificationKind::Commented,
+            todo_id: todo.id,
+            actor_id: Some(author_id.to_string()),
+            message: format!("New comment on \"{}\"", todo.title),
+            dedupe_key: None,
+        })
+    }
+
+    /// Notifies the owner and the assignee once per due date; moving the due
+    /// date produces a new key and so a new notification.
+    pub fn due_passed(todo: &TodoModel) -> Vec<Self> {
+        let Some(due_at) = todo.due_at else {
+            return Vec::new();
+        };
+
+        let mut recipients: Vec<&str> = Vec::with_capacity(2);
+        for user_id in [todo.owner_id.as_deref(), todo.assignee_id.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            if !recipients.contains(&user_id) {
+                recipients.push(user_id);
+            }
+        }
+
+        recipients
+            .into_iter()
+            .map(|user_id| Self {
+                user_id: user_id.to_string(),
+                kind: NotificationKind::DuePassed,
+                todo_id: todo.id,
+                actor_id: None,
+                message: format!("\"{}\" is past due", todo.title),
+                dedupe_key: Some(format!(
+                    "due_passed:{}:{}:{}",
+                    todo.id,
+                    user_id,
+                    due_at.timestamp()
+                )),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    Assigned,
+    Commented,
+    DuePassed,
+}
+
+impl From<NotificationKind> for NotificationType {
+    fn from(kind: NotificationKind) -> Self {
+        match kind {
+            NotificationKind::Assigned => NotificationType::Assigned,
+            NotificationKind::Commented => NotificationType::Commented,
+            NotificationKind::DuePassed => NotificationType::DuePassed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub kind: NotificationType,
+    pub todo_id: Uuid,
+    pub actor_id: Option<String>,
+    pub message: String,
+    #[serde(serialize_with = "timestamp")]
+    pub read_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for NotificationResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            kind: model.kind.into(),
+            todo_id: model.todo_id,
+            actor_id: model.actor_id,
+            message: model.message,
+            read_at: model.read_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct NotificationListQuery {
+    #[serde(default)]
+    pub unread: bool,
+    #[validate(range(min = 1, max = 200))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct UnreadCountResponse {
+    pub unread: u64,
+}
+
+#[derive(Serialize)]
+pub struct MarkAllReadResponse {
+    pub updated: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use entity::sea_orm_active_enums::TodoStatus;
+
+    fn todo(owner_id: Option<&str>, assignee_id: Option<&str>) -> TodoModel {
+        let now = Utc::now().fixed_offset();
+        TodoModel {
+            id: Uuid::new_v4(),
+            title: "Pay rent".to_string(),
+            created_at: now,
+            updated_at: now,
+            change_seq: 1,
+            completed_at: None,
+            archived_at: None,
+            status: TodoStatus::Todo,
+            project_id: None,
+            owner_id: owner_id.map(String::from),
+            due_at: Some(now),
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: assignee_id.map(String::from),
+            remind_at: None,
+            reminded_at: None,
+            parent_id: None,
+            position: None,
+            description: None,
+            number: 1,
+        }
+    }
+
+    #[test]
+    fn test_self_actions_do_not_notify() {
+        let todo = todo(Some("alice"), Some("alice"));
+
+        assert!(NewNotification::assigned(&todo, Some("alice")).is_none());
+        assert!(NewNotification::commented(&todo, "alice").is_none());
+        assert!(NewNotification::assigned(&todo, Some("bob")).is_some());
+        assert!(NewNotification::commented(&todo, "bob").is_some());
+    }
+
+    #[test]
+    fn test_due_passed_notifies_owner_and_assignee_once() {
+        let shared = todo(Some("alice"), Some("bob"));
+        let own = todo(Some("alice"), Some("alice"));
+        let unowned = todo(None, None);
+
+        let notifications = NewNotification::due_passed(&shared);
+        let users: Vec<&str> = notifications.iter().map(|n| n.user_id.as_str()).collect();
+
+        assert_eq!(users, vec!["alice", "bob"]);
+        assert_ne!(notifications[0].dedupe_key, notifications[1].dedupe_key);
+        assert_eq!(NewNotification::due_passed(&own).len(), 1);
+        assert!(NewNotification::due_passed(&unowned).is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use tracing::info;
+
+use crate::job::handler::Job;
+
+use super::repository::NotificationRepository;
+use super::service::NotificationService;
+
+/// Notifies owners and assignees of todos whose due date has passed. Looks
+/// back over a window rather than since the last run, so missed runs are
+/// caught up and duplicates are absorbed by dedupe keys.
+pub struct NotifyOverdueTodos<R: NotificationRepository> {
+    service: NotificationService<R>,
+    lookback: Duration,
+}
+
+impl<R: NotificationRepository> NotifyOverdueTodos<R> {
+    pub fn new(service: NotificationService<R>, lookback_hours: i64) -> Self {
+        Self {
+            service,
+            lookback: Duration::hours(lookback_hours),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: NotificationRepository + 'static> Job for NotifyOverdueTodos<R> {
+    fn name(&self) -> &'static str {
+        "notifications.overdue"
+    }
+
+    async fn run(&self, _payload: Value) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let created = self
+            .service
+            .notify_overdue(now - self.lookback, now)
+            .await?;
+
+        info!(created, "Created overdue notifications");
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::notification::{ActiveModel, Column, Entity, Model};
+use entity::sea_orm_active_enums::TodoStatus;
+use entity::todo::{Column as TodoColumn, Entity as TodoEntity};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use super::model::NewNotification;
+
+/// Writes notifications using the caller's connection, so that they commit
+/// together with the change that caused them. Notifications whose dedupe key
+/// already exists are skipped.
+pub async fn insert_many<C: ConnectionTrait>(
+    conn: &C,
+    notifications: Vec<NewNotification>,
+) -> Result<u64, DbErr> {
+    if notifications.is_empty() {
+        return Ok(0);
+    }
+
+    Entity::insert_many(notifications.into_iter().map(|notification| ActiveModel {
+        user_id: Set(notification.user_id),
+        kind: Set(notification.kind),
+        todo_id: Set(notification.todo_id),
+        actor_id: Set(notification.actor_id),
+        message: Set(notification.message),
+        dedupe_key: Set(notification.dedupe_key),
+        ..Default::default()
+    }))
+    .on_conflict(
+        OnConflict::column(Column::DedupeKey)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await
+}
+
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn list(&self, user_id: &str, unread_only: bool, limit: u64)
+        -> Result<Vec<Model>, DbErr>;
+    async fn unread_count(&self, user_id: &str) -> Result<u64, DbErr>;
+    async fn mark_read(&self, user_id: &str, id: Uuid) -> Result<Model, DbErr>;
+    async fn mark_all_read(&self, user_id: &str) -> Result<u64, DbErr>;
+    async fn notify_overdue(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<u64, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct NotificationRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationRepositoryImpl {
+    async fn list(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        let mut query = Entity::find().filter(Column::UserId.eq(user_id));
+        if unread_only {
+            query = query.filter(Column::ReadAt.is_null());
+        }
+
+        query
+            .order_by_desc(Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn unread_count(&self, user_id: &str) -> Result<u64, DbErr> {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .count(&self.db)
+            .await
+    }
+
+    async fn mark_read(&self, user_id: &str, id: Uuid) -> Result<Model, DbErr> {
+        let updated = Entity::update_many()
+            .col_expr(Column::ReadAt, Utc::now().into())
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .exec_with_returning(&self.db)
+            .await?
+            .into_iter()
+            .next();
+
+        match updated {
+            Some(notification) => Ok(notification),
+            // Already read: keep the first read time.
+            None => Entity::find_by_id(id)
+                .filter(Column::UserId.eq(user_id))
+                .one(&self.db)
+                .await?
+                .ok_or(DbErr::RecordNotFound("Notification not found".to_string())),
+        }
+    }
+
+    async fn mark_all_read(&self, user_id: &str) -> Result<u64, DbErr> {
+        let res = Entity::update_many()
+            .col_expr(Column::ReadAt, Utc::now().into())
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(res.rows_affected)
+    }
+
+    /// Notifies about open todos whose due date passed in `(since, now]`.
+    /// Dedupe keys make repeated sweeps over the same window harmless.
+    async fn notify_overdue(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<u64, DbErr> {
+        let todos = TodoEntity::find()
+            .filter(TodoColumn::DueAt.gt(since))
+            .filter(TodoColumn::DueAt.lte(now))
+            .filter(TodoColumn::Status.is_not_in([TodoStatus::Done, TodoStatus::Cancelled]))
+            .filter(TodoColumn::ArchivedAt.is_null())
+            .all(&self.db)
+            .await?;
+
+        let notifications = todos.iter().flat_map(NewNotification::due_passed).collect();
+        insert_many(&self.db, notifications).await
+    }
+}
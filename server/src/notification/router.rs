@@ -0,0 +1,19 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::state::AppState, middleware::auth::is_authenticated, notification::controller,
+};
+
+pub fn init() -> Router<AppState> {
+    // Each user only ever sees their own inbox.
+    Router::new()
+        .route("/unread-count", get(controller::unread_count))
+        .route("/read", post(controller::mark_all_read))
+        .route("/{notification_id}/read", post(controller::mark_read))
+        .route("/", get(controller::list))
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use entity::notification::Model;
+use uuid::Uuid;
+
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository::NotificationRepository;
+
+const DEFAULT_LIST_LIMIT: u64 = 50;
+
+#[derive(Clone)]
+pub struct NotificationService<R: NotificationRepository> {
+    pub repo: Arc<R>,
+}
+
+impl<R: NotificationRepository> NotificationService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        NotificationService { repo }
+    }
+
+    pub async fn list_notifications(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        limit: Option<u64>,
+    ) -> ServiceResult<Vec<Model>> {
+        self.repo
+            .list(user_id, unread_only, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn unread_count(&self, user_id: &str) -> ServiceResult<u64> {
+        self.repo
+            .unread_count(user_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn mark_read(&self, user_id: &str, id: Uuid) -> ServiceResult<Model> {
+        self.repo
+            .mark_read(user_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn mark_all_read(&self, user_id: &str) -> ServiceResult<u64> {
+        self.repo
+            .mark_all_read(user_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn notify_overdue(
+        &self,
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> ServiceResult<u64> {
+        self.repo
+            .notify_overdue(since, now)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
@@ -0,0 +1,9 @@
+use axum::Json;
+
+use super::spec;
+use crate::common::serialization::JsonPolicy;
+
+/// Spelled like the responses under the configured `JSON_FIELD_NAMING`.
+pub async fn spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(spec::openapi_with(JsonPolicy::current().naming))
+}
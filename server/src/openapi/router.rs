@@ -0,0 +1,19 @@
+use axum::{routing::get, Router};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{
+    common::state::AppState,
+    config::CONFIG,
+    openapi::{controller, spec},
+};
+
+pub fn init() -> Router<AppState> {
+    // Swagger UI is for development; production only serves the spec. Its
+    // assets are compiled into the binary, so the page needs no CDN.
+    if CONFIG.swagger_ui_enabled {
+        let spec = spec::openapi_with(CONFIG.json_field_naming);
+        Router::new().merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", spec))
+    } else {
+        Router::new().route("/api/openapi.json", get(controller::spec))
+    }
+}
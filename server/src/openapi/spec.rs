@@ -0,0 +1,181 @@
+use utoipa::openapi::schema::{AdditionalProperties, ArrayItems, Schema};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::RefOr;
+use utoipa::{Modify, OpenApi};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::common::error::api::ErrorDetail;
+use crate::common::error::ErrorResponse;
+use crate::common::serialization::{to_camel_case, FieldNaming};
+use crate::todo::model::{
+    AddDependencyRequest, AssignTodoRequest, ClientChange, CommentResponse, CreateCommentRequest,
+    CreateTodoRequest, Priority, QuickAddRequest, QuickAddResponse, RenderFormat, SyncPullResponse,
+    SyncPushRequest, SyncPushResponse, SyncResult, SyncStatus, TodoResponse, TombstoneResponse,
+    UpdateTodoRequest,
+};
+use crate::todo::quick_add::ParsedTodo;
+use crate::todo::status::TodoState;
+
+/// Components, tags and security shared by the todo routes. The paths come
+/// from the router; see [`openapi`].
+#[derive(OpenApi)]
+#[openapi(
+    info(description = "Todo REST API"),
+    components(schemas(
+        TodoResponse,
+        TodoState,
+        Priority,
+        RenderFormat,
+        CreateTodoRequest,
+        UpdateTodoRequest,
+        QuickAddRequest,
+        QuickAddResponse,
+        ParsedTodo,
+        AddDependencyRequest,
+        AssignTodoRequest,
+        CreateCommentRequest,
+        CommentResponse,
+        SyncPullResponse,
+        TombstoneResponse,
+        SyncPushRequest,
+        ClientChange,
+        SyncPushResponse,
+        SyncResult,
+        SyncStatus,
+        ErrorResponse,
+        ErrorDetail,
+    )),
+    modifiers(&BearerAuth, &WithoutLicense),
+    tags(
+        (name = "todos", description = "Todo CRUD, archiving and subtasks"),
+        (name = "dependencies", description = "Blocking relations between todos"),
+        (name = "collaboration", description = "Assignees and comments"),
+        (name = "sync", description = "Offline delta sync"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The document served at `/api/openapi.json`: [`ApiDoc`] plus the operations
+/// registered on [`crate::router::documented`].
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let (_, openapi) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .merge(crate::router::documented())
+        .split_for_parts();
+    openapi
+}
+
+/// [`openapi`] with schema properties spelled the way `naming` writes
+/// response fields. Query parameters accept either spelling and keep theirs.
+pub fn openapi_with(naming: FieldNaming) -> utoipa::openapi::OpenApi {
+    let mut openapi = openapi();
+    if naming == FieldNaming::CamelCase {
+        if let Some(components) = openapi.components.as_mut() {
+            components
+                .schemas
+                .values_mut()
+                .for_each(camel_case_properties);
+        }
+    }
+    openapi
+}
+
+fn camel_case_properties(schema: &mut RefOr<Schema>) {
+    let RefOr::T(schema) = schema else {
+        return;
+    };
+    match schema {
+        Schema::Object(object) => {
+            object.properties = std::mem::take(&mut object.properties)
+                .into_iter()
+                .map(|(name, mut property)| {
+                    camel_case_properties(&mut property);
+                    (to_camel_case(&name), property)
+                })
+                .collect();
+            for name in &mut object.required {
+                *name = to_camel_case(name);
+            }
+            // Values of a map are typed; its keys are data.
+            if let Some(AdditionalProperties::RefOr(values)) =
+                object.additional_properties.as_deref_mut()
+            {
+                camel_case_properties(values);
+            }
+        }
+        Schema::Array(array) => {
+            if let ArrayItems::RefOrSchema(items) = &mut array.items {
+                camel_case_properties(items);
+            }
+        }
+        Schema::OneOf(one_of) => one_of.items.iter_mut().for_each(camel_case_properties),
+        Schema::AllOf(all_of) => all_of.items.iter_mut().for_each(camel_case_properties),
+        Schema::AnyOf(any_of) => any_of.items.iter_mut().for_each(camel_case_properties),
+        _ => {}
+    }
+}
+
+/// JWT bearer scheme, used by routes that record who acted.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// The package declares no license, so drop the empty placeholder that
+/// would otherwise be published.
+struct WithoutLicense;
+
+impl Modify for WithoutLicense {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    /// Fails when the routes or models change without the checked-in spec.
+    /// Run with `UPDATE_OPENAPI_SNAPSHOT=1` to rewrite it.
+    #[test]
+    fn test_spec_matches_snapshot() {
+        let spec = openapi().to_pretty_json().unwrap() + "\n";
+
+        if std::env::var_os("UPDATE_OPENAPI_SNAPSHOT").is_some() {
+            std::fs::write(SNAPSHOT, &spec).unwrap();
+        }
+        let snapshot = std::fs::read_to_string(SNAPSHOT).unwrap_or_default();
+
+        assert!(
+            snapshot == spec,
+            "OpenAPI spec differs from server/openapi.json; rerun the tests with \
+             UPDATE_OPENAPI_SNAPSHOT=1 and commit the result"
+        );
+    }
+
+    #[test]
+    fn test_camel_case_spec_renames_properties() {
+        let spec = serde_json::to_value(openapi_with(FieldNaming::CamelCase)).unwrap();
+        let todo = &spec["components"]["schemas"]["TodoResponse"];
+
+        assert!(todo["properties"]["dueAt"].is_object());
+        assert!(todo["properties"]["due_at"].is_null());
+        assert!(todo["required"]
+            .as_array()
+            .unwrap()
+            .contains(&"trackedSeconds".into()));
+    }
+}
@@ -0,0 +1,15 @@
+use axum::extract::State;
+use tracing::error;
+
+use crate::{common::error::ApiResult, common::negotiate::Negotiated, common::state::AppState};
+
+use super::model::ReplayResponse;
+
+pub async fn replay(State(state): State<AppState>) -> ApiResult<Negotiated<ReplayResponse>> {
+    let requeued = state.outbox_service.replay_dead().await.map_err(|err| {
+        error!("Failed to replay outbox events: {:?}", err);
+        err
+    })?;
+
+    Ok(Negotiated(ReplayResponse { requeued }))
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, FixedOffset};
+use entity::outbox::Model;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for OutboxEvent {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            aggregate_type: model.aggregate_type,
+            aggregate_id: model.aggregate_id,
+            event_type: model.event_type,
+            payload: model.payload,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// An event to be written to the outbox alongside the mutation that caused it.
+#[derive(Debug, Clone)]
+pub struct NewOutboxEvent {
+    pub aggregate_type: &'static str,
+    pub aggregate_id: Uuid,
+    pub event_type: &'static str,
+    pub payload: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayResponse {
+    pub requeued: u64,
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, DbErr};
+use tokio::time::{self, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
+
+use crate::config::Config;
+use crate::job::runner::backoff;
+
+use super::model::OutboxEvent;
+use super::repository;
+use super::sink::{EventBus, LogSink, OutboxSink, OutboxSinkKind, WebhookSink};
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Publishes pending outbox rows to the configured sinks.
+///
+/// Delivery is at-least-once: a row is only marked delivered after every sink
+/// accepted it, so a crash or a failing sink causes the whole row to be retried.
+/// Failed rows back off exponentially until `max_attempts` is reached; they can
+/// then be requeued through `POST /api/v1/admin/outbox/replay`.
+pub struct OutboxRelay {
+    db: DatabaseConnection,
+    sinks: Vec<Arc<dyn OutboxSink>>,
+    poll_interval: Duration,
+    batch_size: u64,
+    max_attempts: i32,
+    backoff_base: Duration,
+    lease: chrono::Duration,
+    retention: chrono::Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(db: DatabaseConnection, config: &Config, bus: EventBus) -> Self {
+        let sinks = config
+            .outbox_sinks
+            .iter()
+            .filter_map(|kind| -> Option<Arc<dyn OutboxSink>> {
+                match kind {
+                    OutboxSinkKind::Bus => Some(Arc::new(bus.clone())),
+                    OutboxSinkKind::Log => Some(Arc::new(LogSink)),
+                    OutboxSinkKind::Webhook => match &config.outbox_webhook_url {
+                        Some(url) => Some(Arc::new(WebhookSink::new(url.clone()))),
+                        None => {
+                            warn!("webhook outbox sink enabled without outbox_webhook_url");
+                            None
+                        }
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            db,
+            sinks,
+            poll_interval: Duration::from_millis(config.outbox_poll_interval_ms),
+            batch_size: config.outbox_batch_size,
+            max_attempts: config.outbox_max_attempts,
+            backoff_base: Duration::from_secs(config.outbox_backoff_base_secs),
+            lease: chrono::Duration::seconds(config.outbox_lease_secs),
+            retention: chrono::Duration::hours(config.outbox_retention_hours),
+        }
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        let mut interval = time::interval(self.poll_interval);
+        let mut last_cleanup = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            loop {
+                match self.relay_batch().await {
+                    Ok(count) if count as u64 == self.batch_size => continue,
+                    Ok(_) => break,
+                    Err(err) => {
+                        error!("Failed to relay outbox batch: {:?}", err);
+                        break;
+                    }
+                }
+            }
+
+            if last_cleanup.elapsed() >= CLEANUP_INTERVAL {
+                last_cleanup = Instant::now();
+                if let Err(err) = self.cleanup().await {
+                    error!("Failed to clean up outbox: {:?}", err);
+                }
+            }
+        }
+    }
+
+    /// Publishes one claimed batch. Sinks are called outside any transaction;
+    /// each outcome is recorded on its own.
+    async fn relay_batch(&self) -> Result<usize, DbErr> {
+        let rows =
+            repository::claim_pending(&self.db, self.batch_size, self.max_attempts, self.lease)
+                .await?;
+        let count = rows.len();
+
+        for row in rows {
+            let attempt = row.attempts + 1;
+            let event = OutboxEvent::from(row);
+            match self.publish(&event).await {
+                Ok(()) => repository::mark_delivered(&self.db, event.id).await?,
+                Err(err) => {
+                    warn!(event_id = %event.id, attempt, "Failed to publish outbox event: {:?}", err);
+                    let retry_at = Utc::now() + backoff(self.backoff_base, attempt);
+                    repository::mark_failed(&self.db, event.id, format!("{err:#}"), retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        for sink in &self.sinks {
+            sink.publish(event)
+                .await
+                .map_err(|err| err.context(format!("sink `{}`", sink.name())))?;
+        }
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DbErr> {
+        let purged = repository::purge_delivered(&self.db, Utc::now() - self.retention).await?;
+        debug!(purged, "Purged delivered outbox events");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use crate::outbox::model::NewOutboxEvent;
+    use async_trait::async_trait;
+    use entity::outbox::Entity;
+    use sea_orm::EntityTrait;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    struct FailingSink;
+
+    #[async_trait]
+    impl OutboxSink for FailingSink {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn publish(&self, _event: &OutboxEvent) -> anyhow::Result<()> {
+            anyhow::bail!("connection refused")
+        }
+    }
+
+    fn relay(db: DatabaseConnection, sink: Arc<dyn OutboxSink>) -> OutboxRelay {
+        OutboxRelay {
+            db,
+            sinks: vec![sink],
+            poll_interval: Duration::from_millis(10),
+            batch_size: 10,
+            max_attempts: 3,
+            backoff_base: Duration::from_secs(60),
+            lease: chrono::Duration::minutes(1),
+            retention: chrono::Duration::hours(1),
+        }
+    }
+
+    async fn insert_event(db: &DatabaseConnection) -> Uuid {
+        let aggregate_id = Uuid::new_v4();
+        let event = NewOutboxEvent {
+            aggregate_type: "todo",
+            aggregate_id,
+            event_type: "todo.created",
+            payload: json!({}),
+        };
+        repository::insert(db, event).await.unwrap();
+        aggregate_id
+    }
+
+    #[tokio::test]
+    async fn test_delivered_events_are_published_once() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let bus = EventBus::new(8);
+        let mut events = bus.subscribe();
+        let aggregate_id = insert_event(&db).await;
+        let relay = relay(db.clone(), Arc::new(bus));
+
+        assert_eq!(relay.relay_batch().await.unwrap(), 1);
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+
+        assert_eq!(events.try_recv().unwrap().aggregate_id, aggregate_id);
+        let row = Entity::find().one(&db).await.unwrap().unwrap();
+        assert!(row.delivered_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_events_back_off() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        insert_event(&db).await;
+        let relay = relay(db.clone(), Arc::new(FailingSink));
+
+        assert_eq!(relay.relay_batch().await.unwrap(), 1);
+        // Not retried straight away, even though the batch was not full.
+        assert_eq!(relay.relay_batch().await.unwrap(), 0);
+
+        let row = Entity::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(row.attempts, 1);
+        assert!(row.delivered_at.is_none());
+        assert!(row.next_attempt_at > Utc::now() + chrono::Duration::seconds(30));
+        assert_eq!(
+            row.last_error.as_deref(),
+            Some("sink `failing`: connection refused")
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Duration, Utc};
+use entity::outbox::{ActiveModel, Column, Entity, Model};
+use sea_orm::sea_query::{Expr, LockBehavior, LockType};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use uuid::Uuid;
+
+use super::model::NewOutboxEvent;
+
+/// Writes an event using the caller's connection, so that it commits or
+/// rolls back together with the surrounding transaction.
+pub async fn insert<C: ConnectionTrait>(conn: &C, event: NewOutboxEvent) -> Result<(), DbErr> {
+    Entity::insert(ActiveModel {
+        aggregate_type: Set(event.aggregate_type.to_string()),
+        aggregate_id: Set(event.aggregate_id),
+        event_type: Set(event.event_type.to_string()),
+        payload: Set(event.payload),
+        ..Default::default()
+    })
+    .exec_without_returning(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Claims the oldest due events for `lease` and commits, so sinks are called
+/// without holding row locks. Events claimed by another relay are skipped, and
+/// a relay that dies mid-batch leaves its events to be claimed again once the
+/// lease runs out.
+pub async fn claim_pending<C: TransactionTrait>(
+    db: &C,
+    limit: u64,
+    max_attempts: i32,
+    lease: Duration,
+) -> Result<Vec<Model>, DbErr> {
+    let now = Utc::now();
+    let txn = db.begin().await?;
+
+    let rows = Entity::find()
+        .filter(Column::DeliveredAt.is_null())
+        .filter(Column::Attempts.lt(max_attempts))
+        .filter(Column::NextAttemptAt.lte(now))
+        .order_by_asc(Column::CreatedAt)
+        .limit(limit)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(&txn)
+        .await?;
+
+    if !rows.is_empty() {
+        Entity::update_many()
+            .col_expr(Column::NextAttemptAt, Expr::value(now + lease))
+            .filter(Column::Id.is_in(rows.iter().map(|row| row.id)))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+    Ok(rows)
+}
+
+pub async fn mark_delivered<C: ConnectionTrait>(conn: &C, id: Uuid) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(Column::DeliveredAt, Expr::value(Utc::now()))
+        .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+        .filter(Column::Id.eq(id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Records a failed attempt; the event is not claimed again before `retry_at`.
+pub async fn mark_failed<C: ConnectionTrait>(
+    conn: &C,
+    id: Uuid,
+    error: String,
+    retry_at: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(Column::Attempts, Expr::col(Column::Attempts).add(1))
+        .col_expr(Column::LastError, Expr::value(error))
+        .col_expr(Column::NextAttemptAt, Expr::value(retry_at))
+        .filter(Column::Id.eq(id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Gives undelivered events that ran out of attempts a fresh set of attempts.
+pub async fn requeue_dead<C: ConnectionTrait>(conn: &C, max_attempts: i32) -> Result<u64, DbErr> {
+    let res = Entity::update_many()
+        .col_expr(Column::Attempts, Expr::value(0))
+        .col_expr(Column::NextAttemptAt, Expr::value(Utc::now()))
+        .filter(Column::DeliveredAt.is_null())
+        .filter(Column::Attempts.gte(max_attempts))
+        .exec(conn)
+        .await?;
+
+    Ok(res.rows_affected)
+}
+
+/// Deletes delivered events older than `before`. Undelivered events are kept
+/// regardless of age so they can be inspected or replayed.
+pub async fn purge_delivered<C: ConnectionTrait>(
+    conn: &C,
+    before: DateTime<Utc>,
+) -> Result<u64, DbErr> {
+    let res = Entity::delete_many()
+        .filter(Column::DeliveredAt.lt(before))
+        .exec(conn)
+        .await?;
+
+    Ok(res.rows_affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use serde_json::json;
+
+    async fn insert_event<C: ConnectionTrait>(conn: &C) {
+        let event = NewOutboxEvent {
+            aggregate_type: "todo",
+            aggregate_id: Uuid::new_v4(),
+            event_type: "todo.created",
+            payload: json!({}),
+        };
+        insert(conn, event).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_claimed_events_are_leased_until_marked() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        insert_event(&db).await;
+        insert_event(&db).await;
+
+        let lease = Duration::minutes(1);
+        let claimed = claim_pending(&db, 10, 3, lease).await.unwrap();
+        assert_eq!(claimed.len(), 2);
+        assert!(claim_pending(&db, 10, 3, lease).await.unwrap().is_empty());
+
+        mark_delivered(&db, claimed[0].id).await.unwrap();
+        mark_failed(&db, claimed[1].id, "down".into(), Utc::now())
+            .await
+            .unwrap();
+
+        let retried = claim_pending(&db, 10, 3, lease).await.unwrap();
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].id, claimed[1].id);
+        assert_eq!(retried[0].attempts, 1);
+        assert_eq!(retried[0].last_error.as_deref(), Some("down"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_events_wait_for_retry_at() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        insert_event(&db).await;
+
+        let lease = Duration::minutes(1);
+        let claimed = claim_pending(&db, 10, 3, lease).await.unwrap();
+        let retry_at = Utc::now() + Duration::minutes(5);
+        mark_failed(&db, claimed[0].id, "down".into(), retry_at)
+            .await
+            .unwrap();
+
+        assert!(claim_pending(&db, 10, 3, lease).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dead_events_are_requeued() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        insert_event(&db).await;
+
+        let lease = Duration::minutes(1);
+        let claimed = claim_pending(&db, 10, 1, lease).await.unwrap();
+        mark_failed(&db, claimed[0].id, "down".into(), Utc::now())
+            .await
+            .unwrap();
+        assert!(claim_pending(&db, 10, 1, lease).await.unwrap().is_empty());
+
+        assert_eq!(requeue_dead(&db, 1).await.unwrap(), 1);
+
+        let replayed = claim_pending(&db, 10, 1, lease).await.unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].attempts, 0);
+    }
+}
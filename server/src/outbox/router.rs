@@ -0,0 +1,14 @@
+use axum::{middleware, routing::post, Router};
+
+use crate::{
+    common::state::AppState,
+    middleware::auth::{is_admin, is_authenticated},
+    outbox::controller,
+};
+
+pub fn init() -> Router<AppState> {
+    Router::new()
+        .route("/replay", post(controller::replay))
+        .layer(middleware::from_fn(is_admin))
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,25 @@
+use sea_orm::DatabaseConnection;
+
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository;
+
+#[derive(Clone)]
+pub struct OutboxService {
+    pub db: DatabaseConnection,
+    pub max_attempts: i32,
+}
+
+impl OutboxService {
+    pub fn new(db: DatabaseConnection, max_attempts: i32) -> Self {
+        OutboxService { db, max_attempts }
+    }
+
+    /// Requeues every undelivered event that used up its attempts, e.g. after
+    /// a sink outage. Returns how many were requeued.
+    pub async fn replay_dead(&self) -> ServiceResult<u64> {
+        repository::requeue_dead(&self.db, self.max_attempts)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
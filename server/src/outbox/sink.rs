@@ -0,0 +1,174 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tracing::info;
+
+use super::model::OutboxEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxSinkKind {
+    Bus,
+    Webhook,
+    Log,
+}
+
+#[async_trait]
+pub trait OutboxSink: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()>;
+}
+
+/// In-process broadcast of delivered events. Subscribers that lag behind
+/// lose the oldest events; they should resync from the database if needed.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<OutboxEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<OutboxEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[async_trait]
+impl OutboxSink for EventBus {
+    fn name(&self) -> &'static str {
+        "bus"
+    }
+
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        // Having no subscribers is not a delivery failure.
+        let _ = self.sender.send(event.clone());
+        Ok(())
+    }
+}
+
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl OutboxSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        // Delivery is at-least-once, so receivers should deduplicate on this id.
+        self.client
+            .post(&self.url)
+            .header("X-Outbox-Event-Id", event.id.to_string())
+            .json(event)
+            .send()
+            .await
+            .context("webhook request failed")?
+            .error_for_status()
+            .context("webhook responded with an error")?;
+
+        Ok(())
+    }
+}
+
+pub struct LogSink;
+
+#[async_trait]
+impl OutboxSink for LogSink {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn publish(&self, event: &OutboxEvent) -> anyhow::Result<()> {
+        info!(
+            event_id = %event.id,
+            event_type = %event.event_type,
+            aggregate_id = %event.aggregate_id,
+            "outbox event: {}",
+            event.payload
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+    use wiremock::matchers::{header_exists, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn event() -> OutboxEvent {
+        OutboxEvent {
+            id: Uuid::new_v4(),
+            aggregate_type: "todo".to_string(),
+            aggregate_id: Uuid::new_v4(),
+            event_type: "todo.created".to_string(),
+            payload: json!({ "title": "test" }),
+            created_at: Utc::now().fixed_offset(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_delivers_to_subscribers() {
+        let bus = EventBus::new(8);
+        let mut rx = bus.subscribe();
+        let event = event();
+
+        bus.publish(&event).await.unwrap();
+
+        assert_eq!(rx.recv().await.unwrap().id, event.id);
+    }
+
+    #[tokio::test]
+    async fn test_bus_without_subscribers_succeeds() {
+        let bus = EventBus::new(8);
+        assert!(bus.publish(&event()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_success() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(header_exists("X-Outbox-Event-Id"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sink = WebhookSink::new(mock_server.uri());
+        assert!(sink.publish(&event()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_server_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let sink = WebhookSink::new(mock_server.uri());
+        assert!(sink.publish(&event()).await.is_err());
+    }
+}
@@ -1,8 +1,70 @@
+use crate::common::negotiate::{negotiate, require_acceptable};
+use crate::common::serialization::JsonPolicy;
 use crate::common::state::AppState;
-use crate::todo;
+use crate::config::CONFIG;
+use crate::middleware::deprecation::{deprecation_headers, DeprecationPolicy};
+use crate::{
+    calendar, graphql, job, mail, notification, openapi, outbox, saved_filter, share, time_entry,
+    todo, todo_template,
+};
 
-use axum::Router;
+use axum::{middleware, Router};
+use utoipa_axum::router::OpenApiRouter;
 
 pub fn init() -> Router<AppState> {
-    Router::new().nest("/api/v1/todos", todo::router::init())
+    // v1 is frozen: the documented routes have a v2 successor and announce
+    // it through `Deprecation` and `Sunset` headers once
+    // `API_V1_DEPRECATED_AT`/`API_V1_SUNSET_AT` are set.
+    let (superseded, _) = documented().split_for_parts();
+    let superseded = superseded.layer(middleware::from_fn_with_state(
+        DeprecationPolicy::v1(&CONFIG),
+        deprecation_headers,
+    ));
+
+    Router::new()
+        .merge(superseded)
+        .nest("/api/v1", v1())
+        .nest("/api/v2", v2())
+        .merge(graphql::router::init())
+        .merge(openapi::router::init())
+        .layer(middleware::from_fn_with_state(
+            JsonPolicy::from_config(&CONFIG),
+            negotiate,
+        ))
+}
+
+/// The routes described by the OpenAPI document. `openapi::spec` builds the
+/// served spec from this same router, so an undocumented or renamed route
+/// cannot slip past the snapshot. Reads no configuration, so the spec can be
+/// built without an environment.
+pub fn documented() -> OpenApiRouter<AppState> {
+    let todos = OpenApiRouter::new()
+        .nest("/todos", todo::router::init())
+        .nest("/projects", todo::router::init_projects());
+
+    OpenApiRouter::new().nest("/api/v1", todos)
+}
+
+/// The rest of v1, which is frozen and not part of the OpenAPI document.
+fn v1() -> Router<AppState> {
+    Router::new()
+        .nest("/admin/jobs", job::router::init())
+        .nest("/admin/outbox", outbox::router::init())
+        .nest("/time-entries", time_entry::router::init())
+        .nest("/filters", saved_filter::router::init())
+        .nest("/shares", share::router::init())
+        .nest("/public", share::router::init_public())
+        .nest("/notifications", notification::router::init())
+        .nest("/email", mail::router::init())
+        .nest("/templates", todo_template::router::init())
+        .layer(middleware::from_fn(require_acceptable))
+        // Serves iCalendar feeds next to its JSON routes, so it applies
+        // `require_acceptable` itself.
+        .nest("/calendar", calendar::router::init())
+}
+
+fn v2() -> Router<AppState> {
+    Router::new()
+        .nest("/todos", todo::v2::router::init())
+        .layer(middleware::from_fn(require_acceptable))
 }
@@ -0,0 +1,149 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, NoContent},
+    Extension,
+};
+use chrono::Utc;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    common::error::{ApiError, ApiResult},
+    common::jwt::Claims,
+    common::negotiate::Negotiated,
+    common::state::AppState,
+    common::validated_body::ValidatedBody,
+    common::validated_path::ValidatedPath,
+    todo::model::TodoResponse,
+};
+
+use super::model::{SaveFilterRequest, SavedFilterResponse};
+
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<Vec<SavedFilterResponse>>> {
+    let filters = state
+        .saved_filter_service
+        .list_filters(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to list saved filters: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        filters.into_iter().map(SavedFilterResponse::from).collect(),
+    ))
+}
+
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(filter_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<SavedFilterResponse>> {
+    let filter = state
+        .saved_filter_service
+        .get_filter(&claims.sub, filter_id)
+        .await
+        .map_err(|err| {
+            error!(filter_id = %filter_id, "Failed to get saved filter: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(SavedFilterResponse::from(filter)))
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<SaveFilterRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let filter = state
+        .saved_filter_service
+        .create_filter(&claims.sub, payload.name.clone(), &payload.expression)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, name = %payload.name, "Failed to create saved filter: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(SavedFilterResponse::from(filter)),
+    ))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(filter_id): ValidatedPath<Uuid>,
+    ValidatedBody(payload): ValidatedBody<SaveFilterRequest>,
+) -> ApiResult<Negotiated<SavedFilterResponse>> {
+    let filter = state
+        .saved_filter_service
+        .update_filter(
+            &claims.sub,
+            filter_id,
+            payload.name.clone(),
+            &payload.expression,
+        )
+        .await
+        .map_err(|err| {
+            error!(filter_id = %filter_id, name = %payload.name, "Failed to update saved filter: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(SavedFilterResponse::from(filter)))
+}
+
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(filter_id): ValidatedPath<Uuid>,
+) -> ApiResult<NoContent> {
+    state
+        .saved_filter_service
+        .delete_filter(&claims.sub, filter_id)
+        .await
+        .map_err(|err| {
+            error!(filter_id = %filter_id, "Failed to delete saved filter: {:?}", err);
+            err
+        })?;
+
+    Ok(NoContent)
+}
+
+/// Evaluates the saved expression now and lists matching todos through the
+/// same filter path as `GET /api/v1/todos`.
+pub async fn todos(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(filter_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<Vec<TodoResponse>>> {
+    let expression = state
+        .saved_filter_service
+        .get_expression(&claims.sub, filter_id)
+        .await
+        .map_err(|err| {
+            error!(filter_id = %filter_id, "Failed to load saved filter: {:?}", err);
+            err
+        })?;
+    let filter = expression
+        .resolve(Utc::now())
+        .map_err(ApiError::BadRequest)?;
+
+    let todos = state
+        .todo_service
+        .get_all_todos(filter)
+        .await
+        .map_err(|err| {
+            error!(filter_id = %filter_id, "Failed to get todos for saved filter: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        todos.into_iter().map(TodoResponse::from).collect(),
+    ))
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::saved_filter::Model;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::common::serialization::timestamp;
+use crate::todo::filter::FilterExpression;
+
+#[derive(Serialize)]
+pub struct SavedFilterResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub expression: serde_json::Value,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for SavedFilterResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            expression: model.expression,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Body of both create and update; updates replace the whole filter.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveFilterRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(custom(function = "validate_expression"))]
+    pub expression: FilterExpression,
+}
+
+/// Rejects expressions that would fail at request time, such as unknown
+/// date expressions.
+fn validate_expression(expression: &FilterExpression) -> Result<(), ValidationError> {
+    expression.resolve(Utc::now()).map(|_| ()).map_err(|err| {
+        let mut error = ValidationError::new("invalid_expression");
+        error.message = Some(err.into());
+        error
+    })
+}
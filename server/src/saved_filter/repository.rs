@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::saved_filter::{ActiveModel, Column, Entity, Model};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SavedFilterRepository: Send + Sync {
+    async fn list(&self, owner_id: &str) -> Result<Vec<Model>, DbErr>;
+    async fn get(&self, owner_id: &str, id: Uuid) -> Result<Model, DbErr>;
+    async fn create(&self, owner_id: &str, name: String, expression: Value)
+        -> Result<Model, DbErr>;
+    async fn update(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        name: String,
+        expression: Value,
+    ) -> Result<Model, DbErr>;
+    async fn delete(&self, owner_id: &str, id: Uuid) -> Result<(), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct SavedFilterRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl SavedFilterRepository for SavedFilterRepositoryImpl {
+    async fn list(&self, owner_id: &str) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::OwnerId.eq(owner_id))
+            .order_by_asc(Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get(&self, owner_id: &str, id: Uuid) -> Result<Model, DbErr> {
+        Entity::find_by_id(id)
+            .filter(Column::OwnerId.eq(owner_id))
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Saved filter not found".to_string()))
+    }
+
+    async fn create(
+        &self,
+        owner_id: &str,
+        name: String,
+        expression: Value,
+    ) -> Result<Model, DbErr> {
+        ActiveModel {
+            owner_id: Set(owner_id.to_string()),
+            name: Set(name),
+            expression: Set(expression),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    async fn update(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        name: String,
+        expression: Value,
+    ) -> Result<Model, DbErr> {
+        let filter: ActiveModel = self.get(owner_id, id).await?.into();
+
+        ActiveModel {
+            name: Set(name),
+            expression: Set(expression),
+            updated_at: Set(DateTime::from(Utc::now())),
+            ..filter
+        }
+        .update(&self.db)
+        .await
+    }
+
+    async fn delete(&self, owner_id: &str, id: Uuid) -> Result<(), DbErr> {
+        let res = Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::OwnerId.eq(owner_id))
+            .exec(&self.db)
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Saved filter not found".to_string()));
+        }
+        Ok(())
+    }
+}
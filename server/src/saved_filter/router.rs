@@ -0,0 +1,19 @@
+use axum::{middleware, routing::get, Router};
+
+use crate::{
+    common::state::AppState, middleware::auth::is_authenticated, saved_filter::controller,
+};
+
+pub fn init() -> Router<AppState> {
+    // Filters belong to the caller, so these routes always require a JWT.
+    Router::new()
+        .route(
+            "/{filter_id}",
+            get(controller::get_by_id)
+                .put(controller::update)
+                .delete(controller::delete),
+        )
+        .route("/{filter_id}/todos", get(controller::todos))
+        .route("/", get(controller::list).post(controller::create))
+        .layer(middleware::from_fn(is_authenticated))
+}
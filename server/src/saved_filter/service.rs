@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use entity::saved_filter::Model;
+use uuid::Uuid;
+
+use crate::common::error::{ServiceError, ServiceResult};
+use crate::todo::filter::FilterExpression;
+
+use super::repository::SavedFilterRepository;
+
+#[derive(Clone)]
+pub struct SavedFilterService<R: SavedFilterRepository> {
+    pub repo: Arc<R>,
+}
+
+impl<R: SavedFilterRepository> SavedFilterService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        SavedFilterService { repo }
+    }
+
+    pub async fn list_filters(&self, owner_id: &str) -> ServiceResult<Vec<Model>> {
+        self.repo.list(owner_id).await.map_err(ServiceError::from)
+    }
+
+    pub async fn get_filter(&self, owner_id: &str, id: Uuid) -> ServiceResult<Model> {
+        self.repo
+            .get(owner_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Loads a saved filter's expression, still unresolved so relative
+    /// dates can be evaluated at request time.
+    pub async fn get_expression(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+    ) -> ServiceResult<FilterExpression> {
+        let filter = self.get_filter(owner_id, id).await?;
+        serde_json::from_value(filter.expression).map_err(|_| ServiceError::ValidationError)
+    }
+
+    pub async fn create_filter(
+        &self,
+        owner_id: &str,
+        name: String,
+        expression: &FilterExpression,
+    ) -> ServiceResult<Model> {
+        self.repo
+            .create(owner_id, name, to_json(expression)?)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn update_filter(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        name: String,
+        expression: &FilterExpression,
+    ) -> ServiceResult<Model> {
+        self.repo
+            .update(owner_id, id, name, to_json(expression)?)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn delete_filter(&self, owner_id: &str, id: Uuid) -> ServiceResult<()> {
+        self.repo
+            .delete(owner_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+fn to_json(expression: &FilterExpression) -> ServiceResult<serde_json::Value> {
+    serde_json::to_value(expression).map_err(|_| ServiceError::ValidationError)
+}
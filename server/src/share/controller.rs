@@ -0,0 +1,132 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    common::error::{ApiError, ApiResult},
+    common::jwt::Claims,
+    common::negotiate::Negotiated,
+    common::state::AppState,
+    common::validated_body::ValidatedBody,
+    common::validated_path::ValidatedPath,
+};
+
+use super::model::{
+    CreateShareLinkRequest, PublicShareResponse, PublicTodoView, ShareLinkResponse, SharedKind,
+};
+use super::repository::ShareTarget;
+
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<Vec<ShareLinkResponse>>> {
+    let links = state
+        .share_service
+        .list_links(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to list share links: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        links.into_iter().map(ShareLinkResponse::from).collect(),
+    ))
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<CreateShareLinkRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let target = payload
+        .target()
+        .ok_or(ApiError::BadRequest("validation failed".to_string()))?;
+
+    let link = state
+        .share_service
+        .create_link(&claims.sub, target, payload.expires_at)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, target = ?target, "Failed to create share link: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(ShareLinkResponse::from(link)),
+    ))
+}
+
+pub async fn revoke(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(share_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<ShareLinkResponse>> {
+    let link = state
+        .share_service
+        .revoke_link(&claims.sub, share_id)
+        .await
+        .map_err(|err| {
+            error!(share_id = %share_id, "Failed to revoke share link: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(ShareLinkResponse::from(link)))
+}
+
+/// Anonymous read-only view of a shared todo or project list. Responses are
+/// not cached so every view is counted.
+pub async fn open(
+    State(state): State<AppState>,
+    ValidatedPath(token): ValidatedPath<String>,
+) -> ApiResult<impl IntoResponse> {
+    let (link, target) = state.share_service.open_link(&token).await.map_err(|err| {
+        error!("Failed to open share link: {:?}", err);
+        err
+    })?;
+
+    let (kind, todos) = match target {
+        ShareTarget::Todo(todo_id) => {
+            let todo = state
+                .todo_service
+                .get_todo_by_id(todo_id)
+                .await
+                .map_err(|err| {
+                    error!(share_id = %link.id, "Failed to load shared todo: {:?}", err);
+                    err
+                })?;
+            (SharedKind::Todo, vec![todo])
+        }
+        ShareTarget::Project(project_id) => {
+            let todos = state
+                .todo_service
+                .get_project_todos(project_id)
+                .await
+                .map_err(|err| {
+                    error!(share_id = %link.id, "Failed to load shared list: {:?}", err);
+                    err
+                })?;
+            // Todos moved into the project after sharing stay private.
+            let todos = todos
+                .into_iter()
+                .filter(|todo| todo.owner_id.as_deref() == Some(link.owner_id.as_str()))
+                .collect();
+            (SharedKind::List, todos)
+        }
+    };
+
+    Ok((
+        [(header::CACHE_CONTROL, "no-store")],
+        Negotiated(PublicShareResponse {
+            kind,
+            todos: todos.into_iter().map(PublicTodoView::from).collect(),
+            expires_at: link.expires_at,
+        }),
+    ))
+}
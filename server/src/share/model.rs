@@ -0,0 +1,146 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::share_link::Model;
+use entity::todo::Model as TodoModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::common::serialization::timestamp;
+use crate::todo::model::Priority;
+use crate::todo::status::TodoState;
+
+use super::repository::ShareTarget;
+
+#[derive(Serialize)]
+pub struct ShareLinkResponse {
+    pub id: Uuid,
+    pub token: String,
+    pub url: String,
+    pub todo_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    #[serde(serialize_with = "timestamp")]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+    pub access_count: i64,
+    #[serde(serialize_with = "timestamp")]
+    pub last_accessed_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<Model> for ShareLinkResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            url: format!("/api/v1/public/{}", model.token),
+            token: model.token,
+            todo_id: model.todo_id,
+            project_id: model.project_id,
+            expires_at: model.expires_at,
+            revoked_at: model.revoked_at,
+            access_count: model.access_count,
+            last_accessed_at: model.last_accessed_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// Shares either a single todo or a project list.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_share_request"))]
+pub struct CreateShareLinkRequest {
+    pub todo_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    /// The link stops working after this instant; `None` never expires.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CreateShareLinkRequest {
+    pub fn target(&self) -> Option<ShareTarget> {
+        ShareTarget::from_ids(self.todo_id, self.project_id)
+    }
+}
+
+fn validate_share_request(request: &CreateShareLinkRequest) -> Result<(), ValidationError> {
+    if request.target().is_none() {
+        return Err(ValidationError::new(
+            "exactly one of todo_id and project_id is required",
+        ));
+    }
+    if request.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(ValidationError::new("expires_at must be in the future"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedKind {
+    Todo,
+    List,
+}
+
+/// What an anonymous visitor sees: no ids, owners or tracking data.
+#[derive(Serialize)]
+pub struct PublicShareResponse {
+    pub kind: SharedKind,
+    pub todos: Vec<PublicTodoView>,
+    #[serde(serialize_with = "timestamp")]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicTodoView {
+    pub title: String,
+    pub status: TodoState,
+    pub completed: bool,
+    #[serde(serialize_with = "timestamp")]
+    pub due_at: Option<DateTime<FixedOffset>>,
+    pub priority: Option<Priority>,
+}
+
+impl From<TodoModel> for PublicTodoView {
+    fn from(model: TodoModel) -> Self {
+        let status: TodoState = model.status.into();
+        Self {
+            title: model.title,
+            completed: status == TodoState::Done,
+            status,
+            due_at: model.due_at,
+            priority: model.priority.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn request(todo_id: Option<Uuid>, project_id: Option<Uuid>) -> CreateShareLinkRequest {
+        CreateShareLinkRequest {
+            todo_id,
+            project_id,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_request_requires_exactly_one_target() {
+        let id = Uuid::new_v4();
+
+        assert!(request(Some(id), None).validate().is_ok());
+        assert!(request(None, Some(id)).validate().is_ok());
+        assert!(request(None, None).validate().is_err());
+        assert!(request(Some(id), Some(id)).validate().is_err());
+    }
+
+    #[test]
+    fn test_request_rejects_past_expiry() {
+        let mut request = request(Some(Uuid::new_v4()), None);
+        request.expires_at = Some(Utc::now() - Duration::minutes(1));
+
+        assert!(request.validate().is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::share_link::{ActiveModel, Column, Entity, Model};
+use entity::todo::{Column as TodoColumn, Entity as TodoEntity};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+/// What a share link exposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    Todo(Uuid),
+    Project(Uuid),
+}
+
+impl ShareTarget {
+    /// Exactly one of the two ids must be set.
+    pub fn from_ids(todo_id: Option<Uuid>, project_id: Option<Uuid>) -> Option<Self> {
+        match (todo_id, project_id) {
+            (Some(todo_id), None) => Some(ShareTarget::Todo(todo_id)),
+            (None, Some(project_id)) => Some(ShareTarget::Project(project_id)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewShareLink {
+    pub owner_id: String,
+    pub token: String,
+    pub target: ShareTarget,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait ShareRepository: Send + Sync {
+    async fn list(&self, owner_id: &str) -> Result<Vec<Model>, DbErr>;
+    async fn create(&self, link: NewShareLink) -> Result<Model, DbErr>;
+    async fn revoke(&self, owner_id: &str, id: Uuid) -> Result<Model, DbErr>;
+    async fn record_access(&self, token: &str, now: DateTime<Utc>) -> Result<Model, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct ShareRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl ShareRepository for ShareRepositoryImpl {
+    async fn list(&self, owner_id: &str) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::OwnerId.eq(owner_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn create(&self, link: NewShareLink) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let (todo_id, project_id) = match link.target {
+            ShareTarget::Todo(todo_id) => {
+                // Only the owner may share a todo; owner-less ones can't be shared.
+                TodoEntity::find_by_id(todo_id)
+                    .filter(TodoColumn::OwnerId.eq(link.owner_id.as_str()))
+                    .one(&txn)
+                    .await?
+                    .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))?;
+                (Some(todo_id), None)
+            }
+            ShareTarget::Project(project_id) => {
+                // Projects have no owner of their own, so the caller must own
+                // every todo in it.
+                let todos = TodoEntity::find()
+                    .filter(TodoColumn::ProjectId.eq(project_id))
+                    .count(&txn)
+                    .await?;
+                let foreign = TodoEntity::find()
+                    .filter(TodoColumn::ProjectId.eq(project_id))
+                    .filter(
+                        Condition::any()
+                            .add(TodoColumn::OwnerId.is_null())
+                            .add(TodoColumn::OwnerId.ne(link.owner_id.as_str())),
+                    )
+                    .count(&txn)
+                    .await?;
+                if todos == 0 || foreign > 0 {
+                    return Err(DbErr::RecordNotFound("Project not found".to_string()));
+                }
+                (None, Some(project_id))
+            }
+        };
+
+        let created = Entity::insert(ActiveModel {
+            token: Set(link.token),
+            owner_id: Set(link.owner_id),
+            todo_id: Set(todo_id),
+            project_id: Set(project_id),
+            expires_at: Set(link.expires_at.map(Into::into)),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    async fn revoke(&self, owner_id: &str, id: Uuid) -> Result<Model, DbErr> {
+        let revoked = Entity::update_many()
+            .col_expr(Column::RevokedAt, Utc::now().into())
+            .filter(Column::Id.eq(id))
+            .filter(Column::OwnerId.eq(owner_id))
+            .filter(Column::RevokedAt.is_null())
+            .exec_with_returning(&self.db)
+            .await?
+            .into_iter()
+            .next();
+
+        match revoked {
+            Some(link) => Ok(link),
+            // Revoking twice keeps the original revocation time.
+            None => Entity::find_by_id(id)
+                .filter(Column::OwnerId.eq(owner_id))
+                .one(&self.db)
+                .await?
+                .ok_or(DbErr::RecordNotFound("Share link not found".to_string())),
+        }
+    }
+
+    /// Counts an access and returns the link in one statement, matching only
+    /// links that are neither revoked nor expired at `now`.
+    async fn record_access(&self, token: &str, now: DateTime<Utc>) -> Result<Model, DbErr> {
+        Entity::update_many()
+            .col_expr(Column::AccessCount, Expr::col(Column::AccessCount).add(1))
+            .col_expr(Column::LastAccessedAt, now.into())
+            .filter(Column::Token.eq(token))
+            .filter(Column::RevokedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(Column::ExpiresAt.is_null())
+                    .add(Column::ExpiresAt.gt(now)),
+            )
+            .exec_with_returning(&self.db)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(DbErr::RecordNotFound("Share link not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use crate::todo::repository::{NewTodo, TodoRepository, TodoRepositoryImpl};
+
+    fn link(owner_id: &str, target: ShareTarget) -> NewShareLink {
+        NewShareLink {
+            owner_id: owner_id.to_string(),
+            token: Uuid::new_v4().to_string(),
+            target,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_owning_the_shared_todos() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let todos = TodoRepositoryImpl { db: db.clone() };
+        let project_id = Uuid::new_v4();
+        let todo = |owner_id: Option<&str>| NewTodo {
+            title: "Shared".to_string(),
+            owner_id: owner_id.map(String::from),
+            project_id: Some(project_id),
+            ..Default::default()
+        };
+        let owned = todos.create(todo(Some("alice"))).await.unwrap();
+        let ownerless = todos.create(todo(None)).await.unwrap();
+        let repo = ShareRepositoryImpl { db };
+
+        repo.create(link("alice", ShareTarget::Todo(owned.id)))
+            .await
+            .unwrap();
+        for (owner_id, target) in [
+            ("mallory", ShareTarget::Todo(owned.id)),
+            ("alice", ShareTarget::Todo(ownerless.id)),
+            ("alice", ShareTarget::Project(project_id)),
+            ("alice", ShareTarget::Project(Uuid::new_v4())),
+        ] {
+            let refused = repo.create(link(owner_id, target)).await;
+            assert!(
+                matches!(refused, Err(DbErr::RecordNotFound(_))),
+                "{owner_id} sharing {target:?}"
+            );
+        }
+
+        todos.delete(ownerless.id).await.unwrap();
+        repo.create(link("alice", ShareTarget::Project(project_id)))
+            .await
+            .unwrap();
+    }
+}
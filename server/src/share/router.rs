@@ -0,0 +1,20 @@
+use axum::{
+    middleware,
+    routing::{delete, get},
+    Router,
+};
+
+use crate::{common::state::AppState, middleware::auth::is_authenticated, share::controller};
+
+pub fn init() -> Router<AppState> {
+    Router::new()
+        .route("/{share_id}", delete(controller::revoke))
+        .route("/", get(controller::list).post(controller::create))
+        .layer(middleware::from_fn(is_authenticated))
+}
+
+/// Deliberately without `is_authenticated`: the token in the URL is the
+/// only credential, and the view it grants is redacted and read-only.
+pub fn init_public() -> Router<AppState> {
+    Router::new().route("/{token}", get(controller::open))
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use entity::share_link::Model;
+use uuid::Uuid;
+
+use crate::common::error::{ServiceError, ServiceResult};
+use crate::common::token;
+
+use super::repository::{NewShareLink, ShareRepository, ShareTarget};
+
+#[derive(Clone)]
+pub struct ShareService<R: ShareRepository> {
+    pub repo: Arc<R>,
+}
+
+impl<R: ShareRepository> ShareService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        ShareService { repo }
+    }
+
+    pub async fn list_links(&self, owner_id: &str) -> ServiceResult<Vec<Model>> {
+        self.repo.list(owner_id).await.map_err(ServiceError::from)
+    }
+
+    pub async fn create_link(
+        &self,
+        owner_id: &str,
+        target: ShareTarget,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> ServiceResult<Model> {
+        self.repo
+            .create(NewShareLink {
+                owner_id: owner_id.to_string(),
+                token: token::generate(),
+                target,
+                expires_at,
+            })
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn revoke_link(&self, owner_id: &str, id: Uuid) -> ServiceResult<Model> {
+        self.repo
+            .revoke(owner_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Resolves a public token and counts the access. Unknown, revoked and
+    /// expired tokens are indistinguishable to the caller.
+    pub async fn open_link(&self, token: &str) -> ServiceResult<(Model, ShareTarget)> {
+        let link = self
+            .repo
+            .record_access(token, Utc::now())
+            .await
+            .map_err(ServiceError::from)?;
+        let target =
+            ShareTarget::from_ids(link.todo_id, link.project_id).ok_or(ServiceError::NotFound)?;
+
+        Ok((link, target))
+    }
+}
@@ -0,0 +1,96 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension};
+use tracing::error;
+
+use crate::{
+    common::error::ApiResult, common::jwt::Claims, common::negotiate::Negotiated,
+    common::state::AppState, common::validated_body::ValidatedBody,
+    common::validated_query::ValidatedQuery,
+};
+
+use super::model::{
+    CreateTimeEntryRequest, StartTimerRequest, TimeEntryResponse, TimeReportQuery,
+    TimeReportResponse, TimeReportRow,
+};
+
+pub async fn start(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<StartTimerRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let entry = state
+        .time_entry_service
+        .start_timer(&claims.sub, payload.todo_id)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, todo_id = %payload.todo_id, "Failed to start timer: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(TimeEntryResponse::from(entry)),
+    ))
+}
+
+pub async fn stop(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<TimeEntryResponse>> {
+    let entry = state
+        .time_entry_service
+        .stop_timer(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to stop timer: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TimeEntryResponse::from(entry)))
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<CreateTimeEntryRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let todo_id = payload.todo_id;
+    let entry = state
+        .time_entry_service
+        .log_time(payload.into_new_entry(claims.sub.clone()))
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, todo_id = %todo_id, "Failed to log time: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(TimeEntryResponse::from(entry)),
+    ))
+}
+
+pub async fn report(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedQuery(query): ValidatedQuery<TimeReportQuery>,
+) -> ApiResult<Negotiated<TimeReportResponse>> {
+    let report = state
+        .time_entry_service
+        .report(&claims.sub, query.from, query.to, query.group_by)
+        .await
+        .map_err(|err| {
+            error!(user_id = %claims.sub, "Failed to build time report: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TimeReportResponse {
+        from: query.from,
+        to: query.to,
+        rows: report
+            .rows
+            .into_iter()
+            .map(|(key, seconds)| TimeReportRow { key, seconds })
+            .collect(),
+        total_seconds: report.total_seconds,
+    }))
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use entity::time_entry::Model;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::common::serialization::timestamp;
+
+use super::repository::NewTimeEntry;
+use super::service::ReportGrouping;
+
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[derive(Serialize)]
+pub struct TimeEntryResponse {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    #[serde(serialize_with = "timestamp")]
+    pub started_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub ended_at: Option<DateTime<FixedOffset>>,
+    pub duration_seconds: Option<i64>,
+    pub note: Option<String>,
+}
+
+impl From<Model> for TimeEntryResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            todo_id: model.todo_id,
+            started_at: model.started_at,
+            ended_at: model.ended_at,
+            duration_seconds: model
+                .ended_at
+                .map(|ended_at| (ended_at - model.started_at).num_seconds()),
+            note: model.note,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StartTimerRequest {
+    pub todo_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_entry_range"))]
+pub struct CreateTimeEntryRequest {
+    pub todo_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+impl CreateTimeEntryRequest {
+    pub fn into_new_entry(self, user_id: String) -> NewTimeEntry {
+        NewTimeEntry {
+            todo_id: self.todo_id,
+            user_id,
+            started_at: self.started_at,
+            ended_at: Some(self.ended_at),
+            note: self.note,
+        }
+    }
+}
+
+fn validate_entry_range(request: &CreateTimeEntryRequest) -> Result<(), ValidationError> {
+    if request.ended_at <= request.started_at {
+        return Err(ValidationError::new("ended_at must be after started_at"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_report_range"))]
+pub struct TimeReportQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    #[serde(default)]
+    pub group_by: ReportGrouping,
+}
+
+fn validate_report_range(query: &TimeReportQuery) -> Result<(), ValidationError> {
+    let days = (query.to - query.from).num_days();
+    if !(0..MAX_REPORT_DAYS).contains(&days) {
+        return Err(ValidationError::new(
+            "to must not be before from and the range must not exceed 366 days",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct TimeReportResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub rows: Vec<TimeReportRow>,
+    pub total_seconds: i64,
+}
+
+#[derive(Serialize)]
+pub struct TimeReportRow {
+    /// Day (`YYYY-MM-DD`) or tag; `null` groups untagged todos.
+    pub key: Option<String>,
+    pub seconds: i64,
+}
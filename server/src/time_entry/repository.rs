@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::time_entry::{ActiveModel, Column, Entity, Model};
+use entity::todo::{Column as TodoColumn, Entity as TodoEntity};
+use entity::todo_tag::{Column as TagColumn, Entity as TagEntity};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct NewTimeEntry {
+    pub todo_id: Uuid,
+    pub user_id: String,
+    pub started_at: DateTime<Utc>,
+    /// `None` starts a running timer.
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+#[async_trait]
+pub trait TimeEntryRepository: Send + Sync {
+    async fn get_running(&self, user_id: &str) -> Result<Option<Model>, DbErr>;
+    async fn create(&self, entry: NewTimeEntry) -> Result<Model, DbErr>;
+    async fn stop_running(&self, user_id: &str, ended_at: DateTime<Utc>) -> Result<Model, DbErr>;
+    async fn list_with_tags(
+        &self,
+        user_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(Model, Vec<String>)>, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct TimeEntryRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl TimeEntryRepository for TimeEntryRepositoryImpl {
+    async fn get_running(&self, user_id: &str) -> Result<Option<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::EndedAt.is_null())
+            .one(&self.db)
+            .await
+    }
+
+    async fn create(&self, entry: NewTimeEntry) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        // Time is only tracked on the user's own or assigned todos.
+        TodoEntity::find_by_id(entry.todo_id)
+            .filter(
+                Condition::any()
+                    .add(TodoColumn::OwnerId.eq(entry.user_id.as_str()))
+                    .add(TodoColumn::AssigneeId.eq(entry.user_id.as_str())),
+            )
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))?;
+
+        let created = Entity::insert(ActiveModel {
+            todo_id: Set(entry.todo_id),
+            user_id: Set(entry.user_id),
+            started_at: Set(entry.started_at.into()),
+            ended_at: Set(entry.ended_at.map(Into::into)),
+            note: Set(entry.note),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    async fn stop_running(&self, user_id: &str, ended_at: DateTime<Utc>) -> Result<Model, DbErr> {
+        Entity::update_many()
+            .col_expr(Column::EndedAt, ended_at.into())
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::EndedAt.is_null())
+            .exec_with_returning(&self.db)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(DbErr::RecordNotFound("No running timer".to_string()))
+    }
+
+    async fn list_with_tags(
+        &self,
+        user_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(Model, Vec<String>)>, DbErr> {
+        let entries = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::StartedAt.gte(from))
+            .filter(Column::StartedAt.lt(to))
+            .order_by_asc(Column::StartedAt)
+            .all(&self.db)
+            .await?;
+
+        let todo_ids: Vec<Uuid> = entries.iter().map(|entry| entry.todo_id).collect();
+        let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for tag in TagEntity::find()
+            .filter(TagColumn::TodoId.is_in(todo_ids))
+            .all(&self.db)
+            .await?
+        {
+            tags.entry(tag.todo_id).or_default().push(tag.tag);
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let entry_tags = tags.get(&entry.todo_id).cloned().unwrap_or_default();
+                (entry, entry_tags)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::database::is_unique_constraint_violation;
+    use crate::common::test_db;
+    use crate::todo::repository::{NewTodo, TodoRepository, TodoRepositoryImpl};
+
+    fn entry(todo_id: Uuid, user_id: &str) -> NewTimeEntry {
+        NewTimeEntry {
+            todo_id,
+            user_id: user_id.to_string(),
+            started_at: Utc::now(),
+            ended_at: None,
+            note: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_owned_or_assigned_todo() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let todo = TodoRepositoryImpl { db: db.clone() }
+            .create(NewTodo {
+                title: "Owned".to_string(),
+                owner_id: Some("alice".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = TimeEntryRepositoryImpl { db };
+
+        let foreign = repo.create(entry(todo.id, "mallory")).await;
+        assert!(matches!(foreign, Err(DbErr::RecordNotFound(_))));
+
+        repo.create(entry(todo.id, "alice")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_second_running_timer_violates_unique_index() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let todo = TodoRepositoryImpl { db: db.clone() }
+            .create(NewTodo {
+                title: "Timed".to_string(),
+                owner_id: Some("alice".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = TimeEntryRepositoryImpl { db };
+
+        repo.create(entry(todo.id, "alice")).await.unwrap();
+        let second = repo.create(entry(todo.id, "alice")).await.unwrap_err();
+
+        assert!(is_unique_constraint_violation(&second));
+    }
+}
@@ -0,0 +1,17 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{common::state::AppState, middleware::auth::is_authenticated, time_entry::controller};
+
+pub fn init() -> Router<AppState> {
+    // Entries belong to the caller, so these routes always require a JWT.
+    Router::new()
+        .route("/", post(controller::create))
+        .route("/start", post(controller::start))
+        .route("/stop", post(controller::stop))
+        .route("/report", get(controller::report))
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use entity::time_entry::Model;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::common::error::database::is_unique_constraint_violation;
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository::{NewTimeEntry, TimeEntryRepository};
+
+#[derive(Clone)]
+pub struct TimeEntryService<R: TimeEntryRepository> {
+    pub repo: Arc<R>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportGrouping {
+    #[default]
+    Day,
+    Tag,
+}
+
+/// Tracked seconds per group. Entries count towards the day they started
+/// (UTC) and towards each tag of their todo; `total_seconds` counts every
+/// entry once.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TimeReport {
+    pub rows: Vec<(Option<String>, i64)>,
+    pub total_seconds: i64,
+}
+
+impl<R: TimeEntryRepository> TimeEntryService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        TimeEntryService { repo }
+    }
+
+    pub async fn start_timer(&self, user_id: &str, todo_id: Uuid) -> ServiceResult<Model> {
+        let running = self
+            .repo
+            .get_running(user_id)
+            .await
+            .map_err(ServiceError::from)?;
+        if running.is_some() {
+            return Err(ServiceError::Conflict("a timer is already running"));
+        }
+
+        let entry = NewTimeEntry {
+            todo_id,
+            user_id: user_id.to_string(),
+            started_at: Utc::now(),
+            ended_at: None,
+            note: None,
+        };
+        // A concurrent start can pass the check above; the unique index on
+        // running entries turns the loser into the same conflict.
+        self.repo.create(entry).await.map_err(|err| {
+            if is_unique_constraint_violation(&err) {
+                ServiceError::Conflict("a timer is already running")
+            } else {
+                ServiceError::from(err)
+            }
+        })
+    }
+
+    pub async fn stop_timer(&self, user_id: &str) -> ServiceResult<Model> {
+        self.repo
+            .stop_running(user_id, Utc::now())
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn log_time(&self, entry: NewTimeEntry) -> ServiceResult<Model> {
+        self.repo.create(entry).await.map_err(ServiceError::from)
+    }
+
+    /// Aggregates the user's entries that started between `from` and `to`,
+    /// both inclusive. Running timers count up to now.
+    pub async fn report(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        grouping: ReportGrouping,
+    ) -> ServiceResult<TimeReport> {
+        let start = from.and_time(Default::default()).and_utc();
+        let end = (to + Days::new(1)).and_time(Default::default()).and_utc();
+
+        let entries = self
+            .repo
+            .list_with_tags(user_id, start, end)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(aggregate(&entries, grouping, Utc::now()))
+    }
+}
+
+fn duration_seconds(entry: &Model, now: DateTime<Utc>) -> i64 {
+    let ended_at = entry.ended_at.map(|at| at.to_utc()).unwrap_or(now);
+    (ended_at - entry.started_at.to_utc()).num_seconds().max(0)
+}
+
+fn aggregate(
+    entries: &[(Model, Vec<String>)],
+    grouping: ReportGrouping,
+    now: DateTime<Utc>,
+) -> TimeReport {
+    let mut groups: BTreeMap<Option<String>, i64> = BTreeMap::new();
+    let mut total_seconds = 0;
+
+    for (entry, tags) in entries {
+        let seconds = duration_seconds(entry, now);
+        total_seconds += seconds;
+
+        match grouping {
+            ReportGrouping::Day => {
+                let day = entry.started_at.to_utc().date_naive().to_string();
+                *groups.entry(Some(day)).or_default() += seconds;
+            }
+            ReportGrouping::Tag if tags.is_empty() => *groups.entry(None).or_default() += seconds,
+            ReportGrouping::Tag => {
+                for tag in tags {
+                    *groups.entry(Some(tag.clone())).or_default() += seconds;
+                }
+            }
+        }
+    }
+
+    TimeReport {
+        rows: groups.into_iter().collect(),
+        total_seconds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(started_at: &str, minutes: i64) -> Model {
+        let started_at = DateTime::parse_from_rfc3339(started_at).unwrap();
+        Model {
+            id: Uuid::new_v4(),
+            todo_id: Uuid::new_v4(),
+            user_id: "user".to_string(),
+            started_at,
+            ended_at: Some(started_at + chrono::Duration::minutes(minutes)),
+            note: None,
+            created_at: started_at,
+        }
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_aggregate_by_day() {
+        let entries = vec![
+            (entry("2024-03-01T09:00:00Z", 30), tags(&[])),
+            (entry("2024-03-01T23:30:00Z", 60), tags(&[])),
+            (entry("2024-03-02T08:00:00Z", 15), tags(&[])),
+        ];
+
+        let report = aggregate(&entries, ReportGrouping::Day, Utc::now());
+
+        assert_eq!(
+            report.rows,
+            vec![
+                (Some("2024-03-01".to_string()), 90 * 60),
+                (Some("2024-03-02".to_string()), 15 * 60),
+            ]
+        );
+        assert_eq!(report.total_seconds, 105 * 60);
+    }
+
+    #[test]
+    fn test_aggregate_by_tag_counts_entry_per_tag() {
+        let entries = vec![
+            (
+                entry("2024-03-01T09:00:00Z", 30),
+                tags(&["client", "design"]),
+            ),
+            (entry("2024-03-01T10:00:00Z", 10), tags(&[])),
+        ];
+
+        let report = aggregate(&entries, ReportGrouping::Tag, Utc::now());
+
+        assert_eq!(
+            report.rows,
+            vec![
+                (None, 10 * 60),
+                (Some("client".to_string()), 30 * 60),
+                (Some("design".to_string()), 30 * 60),
+            ]
+        );
+        assert_eq!(report.total_seconds, 40 * 60);
+    }
+
+    #[test]
+    fn test_running_entry_counts_until_now() {
+        let mut running = entry("2024-03-01T09:00:00Z", 0);
+        running.ended_at = None;
+        let now = DateTime::parse_from_rfc3339("2024-03-01T09:20:00Z")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(duration_seconds(&running, now), 20 * 60);
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use serde_json::Value;
+use tracing::info;
+
+use crate::job::handler::Job;
+
+use super::repository::TodoRepository;
+use super::service::TodoService;
+
+/// Archives completed todos once they are older than the retention window.
+pub struct ArchiveCompletedTodos<R: TodoRepository> {
+    service: TodoService<R>,
+    retention: Duration,
+    batch_size: u64,
+}
+
+impl<R: TodoRepository> ArchiveCompletedTodos<R> {
+    pub fn new(service: TodoService<R>, retention_days: i64, batch_size: u64) -> Self {
+        Self {
+            service,
+            retention: Duration::days(retention_days),
+            batch_size,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository + 'static> Job for ArchiveCompletedTodos<R> {
+    fn name(&self) -> &'static str {
+        "todos.archive_completed"
+    }
+
+    async fn run(&self, _payload: Value) -> anyhow::Result<()> {
+        let archived = self
+            .service
+            .archive_completed(self.retention, self.batch_size)
+            .await?;
+
+        info!(archived, "Archived completed todos");
+        Ok(())
+    }
+}
@@ -2,37 +2,141 @@ use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, NoContent},
-    Json,
+    Extension,
 };
+use chrono::{FixedOffset, Utc};
+use entity::todo::Model;
+use tokio_stream::{Stream, StreamExt};
 use tracing::error;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    common::error::ApiResult, common::state::AppState, common::validated_json::ValidatedJson,
+    common::error::{ApiError, ApiResult, ErrorResponse, ServiceResult},
+    common::jwt::Claims,
+    common::ndjson::NdJson,
+    common::negotiate::Negotiated,
+    common::pagination::{Page, PageQuery, Pagination},
+    common::state::AppState,
+    common::validated_body::ValidatedBody,
     common::validated_path::ValidatedPath,
+    common::validated_query::ValidatedQuery,
 };
 
-use super::model::{CreateTodoRequest, TodoResponse, UpdateTodoRequest};
+use super::filter::FilterExpression;
+use super::model::{
+    AddDependencyRequest, AssignTodoRequest, CommentResponse, CreateCommentRequest,
+    CreateTodoRequest, QuickAddRequest, QuickAddResponse, SyncPullQuery, SyncPullResponse,
+    SyncPushRequest, SyncPushResponse, SyncResult, SyncStatus, TodoListQuery, TodoResponse,
+    TodoViewQuery, TombstoneResponse, UpdateTodoRequest,
+};
+use super::path::{BlockedByPath, TodoId, TodoIdPath};
+use super::quick_add;
+use super::repository::SyncOutcome;
+use super::view::{Embedded, Includes, ShapedTodo, TodoView};
 
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "todos",
+    params(TodoListQuery, PageQuery),
+    responses(
+        (status = 200, description = "Projected by `fields`, with `tags`/`subtasks` added by `include`; wrapped in `{data, meta, links}` with `envelope=true`", body = [TodoResponse],
+            headers(
+                ("Link" = String, description = "RFC 8288 `self`, `next` and `prev` links"),
+                ("X-Total-Count" = u64, description = "Matching todos across all pages, unless enveloped or not counted"),
+            )
+        ),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
 pub async fn get_all(
     State(state): State<AppState>,
-) -> ApiResult<Json<Vec<TodoResponse>>> {
+    ValidatedQuery(query): ValidatedQuery<TodoListQuery>,
+    pagination: Pagination,
+) -> ApiResult<Page<ShapedTodo>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let filter = FilterExpression::try_from(query)
+        .and_then(|expression| expression.resolve(Utc::now()))
+        .map_err(ApiError::BadRequest)?;
     let todos = state
         .todo_service
-        .get_all_todos()
+        .get_todo_page(
+            filter.clone(),
+            pagination.fetch_limit(),
+            pagination.offset(),
+        )
         .await
         .map_err(|err| {
             error!("Failed to get all todos: {:?}", err);
             err
         })?;
+    let total = pagination
+        .total(&todos, || state.todo_service.count_todos(filter))
+        .await
+        .map_err(|err| {
+            error!("Failed to count todos: {:?}", err);
+            err
+        })?;
 
-    Ok(Json(todos.into_iter().map(TodoResponse::from).collect()))
+    Ok(pagination.page(shape(&state, &view, todos).await?, total))
 }
 
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = "todos",
+    params(TodoListQuery),
+    responses(
+        (status = 200, description = "Every matching todo as one JSON line, projected by `fields`; `include` is not supported", content_type = "application/x-ndjson", body = TodoResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<TodoListQuery>,
+) -> ApiResult<NdJson<impl Stream<Item = ServiceResult<ShapedTodo>>>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    if view.include != Includes::default() {
+        return Err(ApiError::BadRequest(
+            "`include` is not supported when exporting".to_string(),
+        ));
+    }
+    let filter = FilterExpression::try_from(query)
+        .and_then(|expression| expression.resolve(Utc::now()))
+        .map_err(ApiError::BadRequest)?;
+
+    let embedded = Embedded::default();
+    let todos = state.todo_service.stream_todos(filter).map(move |row| {
+        row.map(|todo| view.shape(todo, &embedded)).map_err(|err| {
+            error!("Failed to export todos: {:?}", err);
+            err
+        })
+    });
+
+    Ok(NdJson(todos))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{todo_id}",
+    tag = "todos",
+    params(
+        TodoIdPath,
+        TodoViewQuery,
+    ),
+    responses(
+        (status = 200, description = "Projected by `fields`, with `tags`/`subtasks` added by `include`", body = TodoResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_by_id(
     State(state): State<AppState>,
-    ValidatedPath(todo_id): ValidatedPath<Uuid>,
-) -> ApiResult<Json<TodoResponse>> {
+    TodoId(todo_id): TodoId,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+) -> ApiResult<Negotiated<ShapedTodo>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
     let todo = state
         .todo_service
         .get_todo_by_id(todo_id)
@@ -42,45 +146,133 @@ pub async fn get_by_id(
             err
         })?;
 
-    Ok(Json(TodoResponse::from(todo)))
+    Ok(Negotiated(shape_one(&state, &view, todo).await?))
 }
 
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = "todos",
+    params(TodoViewQuery),
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 201, description = "Projected by `fields`, with `tags`/`subtasks` added by `include`", body = TodoResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
 pub async fn create(
     State(state): State<AppState>,
-    ValidatedJson(payload): ValidatedJson<CreateTodoRequest>,
+    claims: Option<Extension<Claims>>,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+    ValidatedBody(payload): ValidatedBody<CreateTodoRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let owner_id = claims.map(|Extension(claims)| claims.sub);
+    let todo = state
+        .todo_service
+        .create_todo(payload.into_new_todo(owner_id))
+        .await
+        .map_err(|err| {
+            error!("Failed to create todo: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(shape_one(&state, &view, todo).await?),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/quick",
+    tag = "todos",
+    params(TodoViewQuery),
+    request_body = QuickAddRequest,
+    responses(
+        (status = 201, description = "Parsed text and the created todo", body = QuickAddResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
+pub async fn quick_add(
+    State(state): State<AppState>,
+    claims: Option<Extension<Claims>>,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+    ValidatedBody(payload): ValidatedBody<QuickAddRequest>,
 ) -> ApiResult<impl IntoResponse> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let offset = FixedOffset::east_opt(payload.utc_offset_minutes * 60)
+        .ok_or_else(|| ApiError::BadRequest("invalid utc_offset_minutes".to_string()))?;
+    let parsed = quick_add::parse(&payload.text, Utc::now().with_timezone(&offset));
+
+    let request = CreateTodoRequest::from(parsed.clone());
+    request.validate()?;
+
+    let owner_id = claims.map(|Extension(claims)| claims.sub);
     let todo = state
         .todo_service
-        .create_todo(payload.title.clone())
+        .create_todo(request.into_new_todo(owner_id))
         .await
         .map_err(|err| {
-            error!(title = %payload.title, "Failed to create todo: {:?}", err);
+            error!(text = %payload.text, "Failed to quick-add todo: {:?}", err);
             err
         })?;
 
-    Ok((StatusCode::CREATED, Json(TodoResponse::from(todo))))
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(QuickAddResponse {
+            parsed,
+            todo: shape_one(&state, &view, todo).await?,
+        }),
+    ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{todo_id}",
+    tag = "todos",
+    params(TodoIdPath, TodoViewQuery),
+    request_body = UpdateTodoRequest,
+    responses(
+        (status = 200, description = "Projected by `fields`, with `tags`/`subtasks` added by `include`", body = TodoResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+        (status = 409, description = "Conflicting change", body = ErrorResponse),
+    )
+)]
 pub async fn update(
     State(state): State<AppState>,
-    ValidatedPath(todo_id): ValidatedPath<Uuid>,
-    ValidatedJson(payload): ValidatedJson<UpdateTodoRequest>,
-) -> ApiResult<Json<TodoResponse>> {
+    TodoId(todo_id): TodoId,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+    ValidatedBody(payload): ValidatedBody<UpdateTodoRequest>,
+) -> ApiResult<Negotiated<ShapedTodo>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let (title, status, completed) = (payload.title.clone(), payload.status, payload.completed);
     let todo = state
         .todo_service
-        .update_todo(todo_id, payload.title.clone(), payload.completed)
+        .update_todo(todo_id, payload.into())
         .await
         .map_err(|err| {
-            error!(todo_id = %todo_id, title = %payload.title, completed = %payload.completed, "Failed to update todo: {:?}", err);
+            error!(todo_id = %todo_id, title = %title, status = ?status, completed = ?completed, "Failed to update todo: {:?}", err);
             err
         })?;
 
-    Ok(Json(TodoResponse::from(todo)))
+    Ok(Negotiated(shape_one(&state, &view, todo).await?))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{todo_id}",
+    tag = "todos",
+    params(TodoIdPath),
+    responses(
+        (status = 204, description = "Todo deleted"),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete(
     State(state): State<AppState>,
-    ValidatedPath(todo_id): ValidatedPath<Uuid>,
+    TodoId(todo_id): TodoId,
 ) -> ApiResult<NoContent> {
     state
         .todo_service
@@ -94,3 +286,406 @@ pub async fn delete(
     Ok(NoContent)
 }
 
+#[utoipa::path(
+    post,
+    path = "/{todo_id}/unarchive",
+    tag = "todos",
+    params(TodoIdPath),
+    responses(
+        (status = 200, description = "Restored todo", body = TodoResponse),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn unarchive(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+) -> ApiResult<Negotiated<TodoResponse>> {
+    let todo = state
+        .todo_service
+        .unarchive_todo(todo_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to unarchive todo: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TodoResponse::from(todo)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{todo_id}/dependencies",
+    tag = "dependencies",
+    params(TodoIdPath),
+    responses(
+        (status = 200, description = "Todos blocking this todo", body = [TodoResponse]),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_dependencies(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+) -> ApiResult<Negotiated<Vec<TodoResponse>>> {
+    let blockers = state
+        .todo_service
+        .get_blockers(todo_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to get todo dependencies: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        blockers.into_iter().map(TodoResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{todo_id}/dependencies",
+    tag = "dependencies",
+    params(TodoIdPath),
+    request_body = AddDependencyRequest,
+    responses(
+        (status = 201, description = "Dependency added"),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+        (status = 409, description = "The dependency would close a cycle", body = ErrorResponse),
+    )
+)]
+pub async fn add_dependency(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+    ValidatedBody(payload): ValidatedBody<AddDependencyRequest>,
+) -> ApiResult<StatusCode> {
+    state
+        .todo_service
+        .add_dependency(todo_id, payload.blocked_by_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, blocked_by_id = %payload.blocked_by_id, "Failed to add todo dependency: {:?}", err);
+            err
+        })?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{todo_id}/dependencies/{blocked_by_id}",
+    tag = "dependencies",
+    params(TodoIdPath, ("blocked_by_id" = Uuid, Path, description = "Blocking todo id")),
+    responses(
+        (status = 204, description = "Dependency removed"),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn remove_dependency(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+    ValidatedPath(BlockedByPath { blocked_by_id }): ValidatedPath<BlockedByPath>,
+) -> ApiResult<NoContent> {
+    state
+        .todo_service
+        .remove_dependency(todo_id, blocked_by_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, blocked_by_id = %blocked_by_id, "Failed to remove todo dependency: {:?}", err);
+            err
+        })?;
+
+    Ok(NoContent)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{todo_id}/assignee",
+    tag = "collaboration",
+    params(TodoIdPath),
+    request_body = AssignTodoRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Assigned todo", body = TodoResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "Todo not found, or not owned by or assigned to the caller", body = ErrorResponse),
+    )
+)]
+pub async fn assign(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    TodoId(todo_id): TodoId,
+    ValidatedBody(payload): ValidatedBody<AssignTodoRequest>,
+) -> ApiResult<Negotiated<TodoResponse>> {
+    let todo = state
+        .todo_service
+        .assign_todo(todo_id, Some(payload.assignee_id), claims.sub)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to assign todo: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TodoResponse::from(todo)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{todo_id}/assignee",
+    tag = "collaboration",
+    params(TodoIdPath),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Unassigned todo", body = TodoResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "Todo not found, or not owned by or assigned to the caller", body = ErrorResponse),
+    )
+)]
+pub async fn unassign(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    TodoId(todo_id): TodoId,
+) -> ApiResult<Negotiated<TodoResponse>> {
+    let todo = state
+        .todo_service
+        .assign_todo(todo_id, None, claims.sub)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to unassign todo: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TodoResponse::from(todo)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{todo_id}/comments",
+    tag = "collaboration",
+    params(TodoIdPath),
+    responses(
+        (status = 200, description = "Comments, oldest first", body = [CommentResponse]),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_comments(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+) -> ApiResult<Negotiated<Vec<CommentResponse>>> {
+    let comments = state
+        .todo_service
+        .list_comments(todo_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to get todo comments: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        comments.into_iter().map(CommentResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{todo_id}/comments",
+    tag = "collaboration",
+    params(TodoIdPath),
+    request_body = CreateCommentRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Created comment", body = CommentResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn add_comment(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    TodoId(todo_id): TodoId,
+    ValidatedBody(payload): ValidatedBody<CreateCommentRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let comment = state
+        .todo_service
+        .add_comment(todo_id, claims.sub, payload.body)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to add todo comment: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(CommentResponse::from(comment)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{todo_id}/subtasks",
+    tag = "todos",
+    params(TodoIdPath, TodoViewQuery),
+    responses(
+        (status = 200, description = "Projected by `fields`, with `tags`/`subtasks` added by `include`", body = [TodoResponse]),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Todo not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_subtasks(
+    State(state): State<AppState>,
+    TodoId(todo_id): TodoId,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+) -> ApiResult<Negotiated<Vec<ShapedTodo>>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let subtasks = state
+        .todo_service
+        .get_subtasks(todo_id)
+        .await
+        .map_err(|err| {
+            error!(todo_id = %todo_id, "Failed to get subtasks: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(shape(&state, &view, subtasks).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{project_id}/todos",
+    tag = "todos",
+    params(("project_id" = Uuid, Path, description = "Project id"), TodoViewQuery),
+    responses(
+        (status = 200, description = "Active todos of the project, blockers first, projected by `fields`, with `tags`/`subtasks` added by `include`", body = [TodoResponse]),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+    )
+)]
+pub async fn get_project_todos(
+    State(state): State<AppState>,
+    ValidatedPath(project_id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<TodoViewQuery>,
+) -> ApiResult<Negotiated<Vec<ShapedTodo>>> {
+    let view = query.view().map_err(ApiError::BadRequest)?;
+    let todos = state
+        .todo_service
+        .get_project_todos(project_id)
+        .await
+        .map_err(|err| {
+            error!(project_id = %project_id, "Failed to get project todos: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(shape(&state, &view, todos).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sync",
+    tag = "sync",
+    params(SyncPullQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Changes since the token", body = SyncPullResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn sync_pull(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedQuery(query): ValidatedQuery<SyncPullQuery>,
+) -> ApiResult<Negotiated<SyncPullResponse>> {
+    let changes = state
+        .todo_service
+        .pull_changes(&claims.sub, query.since, query.limit)
+        .await
+        .map_err(|err| {
+            error!(
+                since = query.since,
+                "Failed to pull todo changes: {:?}", err
+            );
+            err
+        })?;
+
+    Ok(Negotiated(SyncPullResponse {
+        upserts: changes
+            .upserts
+            .into_iter()
+            .map(TodoResponse::from)
+            .collect(),
+        tombstones: changes
+            .tombstones
+            .into_iter()
+            .map(TombstoneResponse::from)
+            .collect(),
+        next_token: changes.next_token,
+        has_more: changes.has_more,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/sync",
+    tag = "sync",
+    request_body = SyncPushRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Outcome of each change", body = SyncPushResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 404, description = "A change targets another user's todo", body = ErrorResponse),
+    )
+)]
+pub async fn sync_push(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<SyncPushRequest>,
+) -> ApiResult<Negotiated<SyncPushResponse>> {
+    let outcomes = state
+        .todo_service
+        .push_changes(&claims.sub, payload.changes)
+        .await
+        .map_err(|err| {
+            error!("Failed to push todo changes: {:?}", err);
+            err
+        })?;
+
+    let results = outcomes
+        .into_iter()
+        .map(|(id, outcome)| {
+            let (status, current) = match outcome {
+                SyncOutcome::Applied(todo) => (SyncStatus::Applied, todo),
+                SyncOutcome::Stale(todo) => (SyncStatus::Stale, todo),
+                SyncOutcome::Blocked(todo) => (SyncStatus::Blocked, todo),
+            };
+            SyncResult {
+                id,
+                status,
+                current: current.map(TodoResponse::from),
+            }
+        })
+        .collect();
+
+    Ok(Negotiated(SyncPushResponse { results }))
+}
+
+/// Applies `?fields=` and `?include=` to `todos`, loading the included
+/// relations for the whole page at once.
+async fn shape(state: &AppState, view: &TodoView, todos: Vec<Model>) -> ApiResult<Vec<ShapedTodo>> {
+    let embedded = state
+        .todo_service
+        .load_embedded(&todos, view.include)
+        .await
+        .map_err(|err| {
+            error!(include = ?view.include, "Failed to load included relations: {:?}", err);
+            err
+        })?;
+
+    Ok(todos
+        .into_iter()
+        .map(|todo| view.shape(todo, &embedded))
+        .collect())
+}
+
+async fn shape_one(state: &AppState, view: &TodoView, todo: Model) -> ApiResult<ShapedTodo> {
+    let mut shaped = shape(state, view, vec![todo]).await?;
+    Ok(shaped.remove(0))
+}
@@ -0,0 +1,234 @@
+use chrono::{DateTime, Datelike, Days, Duration, FixedOffset, Months, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::model::Priority;
+use super::repository::TodoFilter;
+use super::status::TodoState;
+
+/// A todo filter as stored in saved filters and built from list query
+/// parameters. Date bounds are expressions resolved at request time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilterExpression {
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<TodoState>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority: Vec<Priority>,
+    /// Matches todos carrying any of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// Inclusive lower bound on `due_at`, see [`resolve_date`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_after: Option<String>,
+    /// Exclusive upper bound on `due_at`, see [`resolve_date`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_before: Option<String>,
+    /// Offset from UTC that relative dates such as `today` refer to.
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+impl FilterExpression {
+    /// Resolves relative dates against `now` into a concrete filter.
+    pub fn resolve(&self, now: DateTime<Utc>) -> Result<TodoFilter, String> {
+        // Same bounds as the list query, which saved filters and the gRPC
+        // and GraphQL inputs do not pass through.
+        let offset = Some(self.utc_offset_minutes)
+            .filter(|minutes| (-720..=840).contains(minutes))
+            .and_then(|minutes| FixedOffset::east_opt(minutes * 60))
+            .ok_or_else(|| format!("invalid utc_offset_minutes `{}`", self.utc_offset_minutes))?;
+        let now = now.with_timezone(&offset);
+        let resolve = |expr: &Option<String>| {
+            expr.as_deref()
+                .map(|expr| resolve_date(expr, now))
+                .transpose()
+        };
+
+        Ok(TodoFilter {
+            archived: self.archived,
+            statuses: self.status.iter().copied().map(Into::into).collect(),
+            priorities: self.priority.iter().copied().map(Into::into).collect(),
+            tags: self.tags.iter().map(|tag| tag.to_lowercase()).collect(),
+            project_id: self.project_id,
+            due_after: resolve(&self.due_after)?,
+            due_before: resolve(&self.due_before)?,
+        })
+    }
+}
+
+/// Resolves a date expression in the offset of `now`:
+///
+/// - `now`, `today`, `tomorrow`, `yesterday`
+/// - `start_of_week`, `end_of_week`, `start_of_month`, `end_of_month`, where
+///   an end is the first instant of the following week (Monday) or month
+/// - any of the above followed by an offset such as `+3d`, `-2w` or `+12h`;
+///   an offset alone is relative to `now`
+/// - an RFC 3339 timestamp or a `YYYY-MM-DD` date (midnight)
+pub fn resolve_date(expr: &str, now: DateTime<FixedOffset>) -> Result<DateTime<Utc>, String> {
+    let expr = expr.trim();
+    let invalid = || format!("invalid date expression `{expr}`");
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(expr) {
+        return Ok(timestamp.to_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(expr, "%Y-%m-%d") {
+        return midnight(date, now)
+            .map(|at| at.to_utc())
+            .ok_or_else(invalid);
+    }
+
+    let (base, offset) = match expr.find(['+', '-']) {
+        Some(index) => expr.split_at(index),
+        None => (expr, ""),
+    };
+
+    let today = now.date_naive();
+    let weekday = u64::from(today.weekday().num_days_from_monday());
+    let base = match base.to_lowercase().as_str() {
+        "" | "now" => Some(now),
+        "today" => midnight(today, now),
+        "tomorrow" => midnight(today + Days::new(1), now),
+        "yesterday" => midnight(today - Days::new(1), now),
+        "start_of_week" => midnight(today - Days::new(weekday), now),
+        "end_of_week" => midnight(today + Days::new(7 - weekday), now),
+        "start_of_month" => today.with_day(1).and_then(|day| midnight(day, now)),
+        "end_of_month" => today
+            .with_day(1)
+            .and_then(|day| day.checked_add_months(Months::new(1)))
+            .and_then(|day| midnight(day, now)),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+
+    if offset.is_empty() {
+        return Ok(base.to_utc());
+    }
+
+    let (sign, rest) = offset.split_at(1);
+    let (amount, unit) = rest.split_at(rest.len().saturating_sub(1));
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let amount = if sign == "-" { -amount } else { amount };
+    let delta = match unit {
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+
+    base.checked_add_signed(delta)
+        .map(|at| at.to_utc())
+        .ok_or_else(invalid)
+}
+
+fn midnight(date: NaiveDate, now: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+    now.timezone()
+        .from_local_datetime(&date.and_time(Default::default()))
+        .single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 2024-03-06 15:30 at UTC+2.
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2024-03-06T15:30:00+02:00").unwrap()
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().to_utc()
+    }
+
+    #[test]
+    fn test_resolve_date_table() {
+        let cases = vec![
+            ("now", "2024-03-06T13:30:00Z"),
+            ("today", "2024-03-05T22:00:00Z"),
+            ("TOMORROW", "2024-03-06T22:00:00Z"),
+            ("yesterday", "2024-03-04T22:00:00Z"),
+            ("start_of_week", "2024-03-03T22:00:00Z"),
+            ("end_of_week", "2024-03-10T22:00:00Z"),
+            ("start_of_month", "2024-02-29T22:00:00Z"),
+            ("end_of_month", "2024-03-31T22:00:00Z"),
+            ("today+7d", "2024-03-12T22:00:00Z"),
+            ("today-1w", "2024-02-27T22:00:00Z"),
+            ("+12h", "2024-03-07T01:30:00Z"),
+            ("-3d", "2024-03-03T13:30:00Z"),
+            ("2024-04-01", "2024-03-31T22:00:00Z"),
+            ("2024-04-01T08:00:00Z", "2024-04-01T08:00:00Z"),
+        ];
+
+        for (expr, expected) in cases {
+            assert_eq!(resolve_date(expr, now()), Ok(utc(expected)), "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_date_rejects_invalid_expressions() {
+        for expr in ["soon", "today+", "today+3", "today+3y", "+d", "2024-13-01"] {
+            assert!(resolve_date(expr, now()).is_err(), "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_expression_resolves_into_filter() {
+        let expression: FilterExpression = serde_json::from_str(
+            r#"{
+                "status": ["todo", "in_progress"],
+                "priority": ["high"],
+                "tags": ["Finance"],
+                "due_after": "start_of_week",
+                "due_before": "end_of_week",
+                "utc_offset_minutes": 120
+            }"#,
+        )
+        .unwrap();
+
+        let filter = expression.resolve(now().to_utc()).unwrap();
+
+        assert_eq!(filter.statuses.len(), 2);
+        assert_eq!(filter.tags, vec!["finance".to_string()]);
+        assert_eq!(filter.due_after, Some(utc("2024-03-03T22:00:00Z")));
+        assert_eq!(filter.due_before, Some(utc("2024-03-10T22:00:00Z")));
+        assert!(!filter.archived);
+    }
+
+    #[test]
+    fn test_expression_rejects_unknown_fields() {
+        let result = serde_json::from_str::<FilterExpression>(r#"{"colour": "red"}"#);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expression_accepts_camel_case_fields() {
+        use crate::common::serialization::AnyCase;
+
+        let AnyCase(expression) = serde_json::from_str::<AnyCase<FilterExpression>>(
+            r#"{"dueBefore": "+7d", "utcOffsetMinutes": 120}"#,
+        )
+        .unwrap();
+
+        assert_eq!(expression.due_before.as_deref(), Some("+7d"));
+        assert_eq!(expression.utc_offset_minutes, 120);
+        assert!(serde_json::from_str::<AnyCase<FilterExpression>>(r#"{"dueBy": "+7d"}"#).is_err());
+    }
+
+    #[test]
+    fn test_resolve_rejects_out_of_range_offset() {
+        for minutes in [i32::MAX, i32::MIN, 841, -721] {
+            let expression = FilterExpression {
+                utc_offset_minutes: minutes,
+                ..Default::default()
+            };
+
+            assert!(expression.resolve(now().to_utc()).is_err(), "{minutes}");
+        }
+    }
+}
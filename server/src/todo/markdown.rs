@@ -0,0 +1,39 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Renders Markdown to HTML and sanitizes the result, so scripts, event
+/// handlers and `javascript:` links never reach the client.
+pub fn to_safe_html(source: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut unsafe_html = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, Parser::new_ext(source, options));
+
+    ammonia::clean(&unsafe_html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_markdown() {
+        let html = to_safe_html("# Plan\n\n- [x] **draft**\n- [ ] ~~ship~~");
+
+        assert!(html.contains("<h1>Plan</h1>"));
+        assert!(html.contains("<strong>draft</strong>"));
+        assert!(html.contains("<del>ship</del>"));
+    }
+
+    #[test]
+    fn test_strips_scripts_and_event_handlers() {
+        let html = to_safe_html(
+            "<script>alert(1)</script>\n\n<img src=\"a.png\" onerror=\"alert(2)\">\n\n[x](javascript:alert(3))",
+        );
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<img src=\"a.png\">"));
+    }
+}
@@ -1,37 +1,369 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::sea_orm_active_enums::{TodoPriority, TodoStatus};
 use entity::todo::Model;
+use entity::todo_comment::Model as CommentModel;
+use entity::todo_tombstone::Model as TombstoneModel;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 use validator::ValidationError;
 
-#[derive(Serialize)]
+use crate::common::serialization::timestamp;
+
+use super::filter::FilterExpression;
+use super::markdown;
+use super::quick_add::ParsedTodo;
+use super::repository::NewTodo;
+use super::service::TodoUpdate;
+use super::status::TodoState;
+use super::view::{ShapedTodo, TodoView};
+
+const MAX_TAGS: usize = 20;
+const MAX_TAG_LENGTH: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            "urgent" => Ok(Priority::Urgent),
+            other => Err(format!("unknown priority `{other}`")),
+        }
+    }
+}
+
+impl From<TodoPriority> for Priority {
+    fn from(priority: TodoPriority) -> Self {
+        match priority {
+            TodoPriority::Low => Priority::Low,
+            TodoPriority::Medium => Priority::Medium,
+            TodoPriority::High => Priority::High,
+            TodoPriority::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+impl From<Priority> for TodoPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => TodoPriority::Low,
+            Priority::Medium => TodoPriority::Medium,
+            Priority::High => TodoPriority::High,
+            Priority::Urgent => TodoPriority::Urgent,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TodoResponse {
     pub id: Uuid,
+    /// Sequential among the owner's todos; `GET /api/v1/todos/%23<number>`
+    /// resolves it.
+    pub number: i32,
     pub title: String,
+    /// Markdown source.
+    pub description: Option<String>,
+    /// Sanitized HTML, only with `?render=html`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_html: Option<String>,
+    pub status: TodoState,
+    /// Kept for clients predating `status`; true when the status is `done`.
     pub completed: bool,
+    #[serde(serialize_with = "timestamp")]
+    pub completed_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub archived_at: Option<DateTime<FixedOffset>>,
+    pub project_id: Option<Uuid>,
+    #[serde(serialize_with = "timestamp")]
+    pub due_at: Option<DateTime<FixedOffset>>,
+    pub priority: Option<Priority>,
+    /// Sum of finished time entries; a running timer is not included.
+    pub tracked_seconds: i64,
+    pub assignee_id: Option<String>,
+    #[serde(serialize_with = "timestamp")]
+    pub remind_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub reminded_at: Option<DateTime<FixedOffset>>,
+    pub parent_id: Option<Uuid>,
+    /// Order among the parent's subtasks.
+    pub position: Option<i32>,
 }
 
 impl From<Model> for TodoResponse {
     fn from(model: Model) -> Self {
         Self {
             id: model.id,
+            number: model.number,
             title: model.title,
-            completed: model.completed,
+            description: model.description,
+            description_html: None,
+            status: model.status.into(),
+            completed: model.status == TodoStatus::Done,
+            completed_at: model.completed_at,
+            archived_at: model.archived_at,
+            project_id: model.project_id,
+            due_at: model.due_at,
+            priority: model.priority.map(Into::into),
+            tracked_seconds: model.tracked_seconds,
+            assignee_id: model.assignee_id,
+            remind_at: model.remind_at,
+            reminded_at: model.reminded_at,
+            parent_id: model.parent_id,
+            position: model.position,
+        }
+    }
+}
+
+impl TodoResponse {
+    pub fn rendered(mut self, render: Option<RenderFormat>) -> Self {
+        if render == Some(RenderFormat::Html) {
+            self.description_html = self.description.as_deref().map(markdown::to_safe_html);
         }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    Html,
+}
+
+#[derive(Debug, Default, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoViewQuery {
+    pub render: Option<RenderFormat>,
+    /// Comma-separated fields to return.
+    pub fields: Option<String>,
+    /// Comma-separated relations to embed: `tags`, `subtasks`.
+    pub include: Option<String>,
+}
+
+impl TodoViewQuery {
+    pub fn view(&self) -> Result<TodoView, String> {
+        TodoView::parse(self.render, self.fields.as_deref(), self.include.as_deref())
     }
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoListQuery {
+    /// Lists archived todos instead of active ones.
+    #[serde(default)]
+    pub archived: bool,
+    /// Comma-separated statuses.
+    pub status: Option<String>,
+    /// Comma-separated priorities.
+    pub priority: Option<String>,
+    /// Comma-separated tags; todos with any of them match.
+    pub tag: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub due_after: Option<String>,
+    pub due_before: Option<String>,
+    #[serde(default)]
+    #[validate(range(min = -720, max = 840))]
+    pub utc_offset_minutes: i32,
+    pub render: Option<RenderFormat>,
+    pub fields: Option<String>,
+    pub include: Option<String>,
+}
+
+impl TodoListQuery {
+    pub fn view(&self) -> Result<TodoView, String> {
+        TodoView::parse(self.render, self.fields.as_deref(), self.include.as_deref())
+    }
+}
+
+impl TryFrom<TodoListQuery> for FilterExpression {
+    type Error = String;
+
+    fn try_from(query: TodoListQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            archived: query.archived,
+            status: split_list(query.status.as_deref())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            priority: split_list(query.priority.as_deref())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            tags: split_list(query.tag.as_deref()).map(String::from).collect(),
+            project_id: query.project_id,
+            due_after: query.due_after,
+            due_before: query.due_before,
+            utc_offset_minutes: query.utc_offset_minutes,
+        })
+    }
+}
+
+fn split_list(value: Option<&str>) -> impl Iterator<Item = &str> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateTodoRequest {
     #[validate(custom(function = "validate_title_length"))]
     pub title: String,
+    /// Markdown; the maximum length is set by `TODO_DESCRIPTION_MAX_LENGTH`.
+    pub description: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<Priority>,
+    /// When to email a reminder to the owner and assignee.
+    pub remind_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+impl CreateTodoRequest {
+    pub fn into_new_todo(self, owner_id: Option<String>) -> NewTodo {
+        let tags = normalize_tags(self.tags);
+
+        NewTodo {
+            title: self.title,
+            description: self.description,
+            project_id: self.project_id,
+            owner_id,
+            due_at: self.due_at,
+            priority: self.priority.map(Into::into),
+            remind_at: self.remind_at,
+            tags,
+        }
+    }
+}
+
+impl From<ParsedTodo> for CreateTodoRequest {
+    fn from(parsed: ParsedTodo) -> Self {
+        Self {
+            title: parsed.title,
+            description: None,
+            project_id: None,
+            due_at: parsed.due_at,
+            priority: parsed.priority,
+            remind_at: None,
+            tags: parsed.tags,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct QuickAddRequest {
+    #[validate(length(min = 1, max = 1000))]
+    pub text: String,
+    /// Offset of the client's local time from UTC, used for relative dates.
+    #[serde(default)]
+    #[validate(range(min = -720, max = 840))]
+    pub utc_offset_minutes: i32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct QuickAddResponse {
+    pub parsed: ParsedTodo,
+    /// Projected by `fields`, with `tags`/`subtasks` added by `include`.
+    #[schema(value_type = TodoResponse)]
+    pub todo: ShapedTodo,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateTodoRequest {
     #[validate(custom(function = "validate_title_length"))]
     pub title: String,
-    pub completed: bool,
+    /// Markdown; the maximum length is set by `TODO_DESCRIPTION_MAX_LENGTH`.
+    pub description: Option<String>,
+    pub status: Option<TodoState>,
+    /// Legacy flag, ignored when `status` is given.
+    pub completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<Priority>,
+    /// Moving the reminder re-arms it if it was already sent.
+    pub remind_at: Option<DateTime<Utc>>,
+    /// Replaces the tags when given; omitted keeps them.
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+}
+
+impl From<UpdateTodoRequest> for TodoUpdate {
+    fn from(request: UpdateTodoRequest) -> Self {
+        Self {
+            title: request.title,
+            description: request.description,
+            status: request.status.map(Into::into),
+            completed: request.completed,
+            project_id: request.project_id,
+            due_at: request.due_at,
+            priority: request.priority.map(Into::into),
+            remind_at: request.remind_at,
+            tags: request.tags.map(normalize_tags),
+        }
+    }
+}
+
+/// Trims and lowercases tags, dropping duplicates.
+fn normalize_tags(raw: Vec<String>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::with_capacity(raw.len());
+    for tag in raw {
+        let tag = tag.trim().to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddDependencyRequest {
+    pub blocked_by_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AssignTodoRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub assignee_id: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub author_id: String,
+    pub body: String,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<CommentModel> for CommentResponse {
+    fn from(model: CommentModel) -> Self {
+        Self {
+            id: model.id,
+            author_id: model.author_id,
+            body: model.body,
+            created_at: model.created_at,
+        }
+    }
 }
 
 pub fn validate_title_length(title: &str) -> Result<(), ValidationError> {
@@ -43,4 +375,100 @@ pub fn validate_title_length(title: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.len() > MAX_TAGS {
+        return Err(ValidationError::new("too many tags"));
+    }
+
+    let valid = tags.iter().map(|tag| tag.trim()).all(|tag| {
+        !tag.is_empty() && tag.len() <= MAX_TAG_LENGTH && !tag.contains(char::is_whitespace)
+    });
+    if !valid {
+        return Err(ValidationError::new(
+            "tags must be between 1 and 50 characters without spaces",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncPullQuery {
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub since: i64,
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncPullResponse {
+    pub upserts: Vec<TodoResponse>,
+    pub tombstones: Vec<TombstoneResponse>,
+    pub next_token: i64,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TombstoneResponse {
+    pub id: Uuid,
+    #[serde(serialize_with = "timestamp")]
+    pub deleted_at: DateTime<FixedOffset>,
+}
 
+impl From<TombstoneModel> for TombstoneResponse {
+    fn from(model: TombstoneModel) -> Self {
+        Self {
+            id: model.id,
+            deleted_at: model.deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_change_count"))]
+pub struct SyncPushRequest {
+    #[validate(nested)]
+    pub changes: Vec<ClientChange>,
+}
+
+fn validate_change_count(request: &SyncPushRequest) -> Result<(), ValidationError> {
+    if request.changes.len() > 500 {
+        return Err(ValidationError::new("must contain at most 500 changes"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ClientChange {
+    pub id: Uuid,
+    #[validate(custom(function = "validate_title_length"))]
+    pub title: String,
+    pub status: Option<TodoState>,
+    pub completed: Option<bool>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncPushResponse {
+    pub results: Vec<SyncResult>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncResult {
+    pub id: Uuid,
+    pub status: SyncStatus,
+    /// Server state after the push, `None` if the todo does not exist.
+    pub current: Option<TodoResponse>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    Stale,
+    /// Not applied because it would complete a todo with open blockers.
+    Blocked,
+}
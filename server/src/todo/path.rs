@@ -0,0 +1,136 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde::Deserialize;
+use tracing::error;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::common::error::ApiError;
+use crate::common::jwt::Claims;
+use crate::common::state::AppState;
+use crate::common::validated_path::ValidatedPath;
+
+/// The `{todo_id}` segment of a todo route, as documented.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct TodoIdPath {
+    /// Todo id, or `%23<number>` for the caller's todo with that number.
+    pub todo_id: String,
+}
+
+/// The `{blocked_by_id}` segment of a dependency route.
+#[derive(Debug, Deserialize)]
+pub struct BlockedByPath {
+    pub blocked_by_id: Uuid,
+}
+
+/// The todo named by `{todo_id}`: its id, or `#<number>` (sent as
+/// `%23<number>`). Numbers count up per owner, so they are looked up among
+/// the caller's todos, or among todos without an owner for anonymous callers.
+#[derive(Debug, Clone, Copy)]
+pub struct TodoId(pub Uuid);
+
+impl FromRequestParts<AppState> for TodoId {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let ValidatedPath(TodoIdPath { todo_id }) =
+            ValidatedPath::from_request_parts(parts, state).await?;
+
+        let Some(number) = todo_id.strip_prefix('#') else {
+            return todo_id
+                .parse()
+                .map(TodoId)
+                .map_err(|_| ApiError::BadRequest("Invalid path parameter format".to_string()));
+        };
+        let number = number
+            .parse::<i32>()
+            .ok()
+            .filter(|number| *number > 0)
+            .ok_or_else(|| ApiError::BadRequest(format!("Invalid todo number `{todo_id}`")))?;
+
+        let owner_id = parts
+            .extensions
+            .get::<Claims>()
+            .map(|claims| claims.sub.clone());
+        let todo = state
+            .todo_service
+            .get_todo_by_number(owner_id, number)
+            .await
+            .map_err(|err| {
+                error!(number, "Failed to resolve todo number: {:?}", err);
+                err
+            })?;
+
+        Ok(TodoId(todo.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use crate::config::Config;
+    use crate::todo::model::CreateTodoRequest;
+    use axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        http::StatusCode,
+        routing::get,
+        Extension, Router,
+    };
+    use tower::ServiceExt;
+
+    fn config() -> Config {
+        let vars = [
+            ("DATABASE_URL", ""),
+            ("LOG_LEVEL", "info"),
+            ("JWKS_URI", ""),
+            ("JWT_AUDIENCE", ""),
+            ("JWT_ISSUER", ""),
+        ];
+        envy::from_iter(vars.map(|(name, value)| (name.to_string(), value.to_string()))).unwrap()
+    }
+
+    async fn call(app: Router, uri: &str) -> (StatusCode, String) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_todo_id_resolves_the_callers_number() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let state = AppState::new(db, &config());
+        let owner = format!("path-{}", Uuid::new_v4());
+        let request: CreateTodoRequest =
+            serde_json::from_value(serde_json::json!({ "title": "numbered" })).unwrap();
+        let todo = state
+            .todo_service
+            .create_todo(request.into_new_todo(Some(owner.clone())))
+            .await
+            .unwrap();
+
+        let app = Router::new()
+            .route(
+                "/{todo_id}",
+                get(|TodoId(id): TodoId| async move { id.to_string() }),
+            )
+            .layer(Extension(Claims { sub: owner, exp: 0 }))
+            .with_state(state);
+
+        let by_number = call(app.clone(), &format!("/%23{}", todo.number)).await;
+        assert_eq!(by_number, (StatusCode::OK, todo.id.to_string()));
+        let by_id = call(app.clone(), &format!("/{}", todo.id)).await;
+        assert_eq!(by_id, (StatusCode::OK, todo.id.to_string()));
+        let (status, _) = call(app, "/%230").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,376 @@
+use chrono::{
+    DateTime, Datelike, Days, Duration, FixedOffset, Months, NaiveDate, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::common::serialization::timestamp;
+
+use super::model::Priority;
+
+/// Time used when the text names a day but no time.
+const DEFAULT_DUE_TIME: (u32, u32) = (17, 0);
+const TONIGHT: (u32, u32) = (20, 0);
+
+/// What the quick-add parser recognised in a line of text.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ParsedTodo {
+    pub title: String,
+    #[serde(serialize_with = "timestamp")]
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<Priority>,
+    pub tags: Vec<String>,
+}
+
+/// Relative date or timestamp recognised in the text.
+#[derive(Debug, Clone, Copy)]
+enum When {
+    Date(NaiveDate),
+    /// A day with a preferred time, used unless an explicit time is given.
+    Evening(NaiveDate),
+    Exact(DateTime<FixedOffset>),
+}
+
+/// Parses free text such as `Pay rent tomorrow 9am #finance !high`.
+///
+/// Recognised words are removed from the title: `#tags`, `!low`, `!medium`,
+/// `!high` and `!urgent` markers (the last one wins), one date expression and
+/// one time of day, both interpreted in the offset of `now`. Anything else,
+/// including a second date or time, is kept in the title.
+pub fn parse(text: &str, now: DateTime<FixedOffset>) -> ParsedTodo {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut title = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    let mut priority = None;
+    let mut when = None;
+    let mut time = None;
+    let mut i = 0;
+
+    while i < words.len() {
+        let rest = &words[i..];
+
+        if let Some(tag) = parse_tag(rest[0]) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            i += 1;
+        } else if let Some(marker) = parse_priority(rest[0]) {
+            priority = Some(marker);
+            i += 1;
+        } else if let Some((parsed, used)) = when.is_none().then(|| parse_when(rest, now)).flatten()
+        {
+            when = Some(parsed);
+            i += used;
+        } else if let Some((parsed, used)) = time.is_none().then(|| parse_time(rest)).flatten() {
+            time = Some(parsed);
+            i += used;
+        } else {
+            title.push(rest[0]);
+            i += 1;
+        }
+    }
+
+    ParsedTodo {
+        title: title.join(" "),
+        due_at: resolve(when, time, now).map(|at| at.with_timezone(&Utc)),
+        priority,
+        tags,
+    }
+}
+
+fn parse_tag(word: &str) -> Option<String> {
+    let tag = word.strip_prefix('#')?;
+    let valid = !tag.is_empty()
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'));
+
+    valid.then(|| tag.to_lowercase())
+}
+
+fn parse_priority(word: &str) -> Option<Priority> {
+    match word.strip_prefix('!')?.to_lowercase().as_str() {
+        "low" => Some(Priority::Low),
+        "medium" | "med" => Some(Priority::Medium),
+        "high" => Some(Priority::High),
+        "urgent" => Some(Priority::Urgent),
+        _ => None,
+    }
+}
+
+/// Matches a date expression at the start of `words` and returns it with the
+/// number of words it spans.
+fn parse_when(words: &[&str], now: DateTime<FixedOffset>) -> Option<(When, usize)> {
+    let lower: Vec<String> = words.iter().take(3).map(|w| w.to_lowercase()).collect();
+    let today = now.date_naive();
+
+    match lower
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["in", amount, unit, ..] => {
+            let amount: u32 = amount.parse().ok()?;
+            let when = match unit.trim_end_matches('s') {
+                "minute" | "min" => {
+                    When::Exact(now.checked_add_signed(Duration::minutes(amount.into()))?)
+                }
+                "hour" | "hr" => {
+                    When::Exact(now.checked_add_signed(Duration::hours(amount.into()))?)
+                }
+                "day" => When::Date(today.checked_add_days(Days::new(amount.into()))?),
+                "week" => When::Date(today.checked_add_days(Days::new(u64::from(amount) * 7))?),
+                "month" => When::Date(today.checked_add_months(Months::new(amount))?),
+                _ => return None,
+            };
+            Some((when, 3))
+        }
+        ["next", "week", ..] => {
+            let days_to_monday = 7 - u64::from(today.weekday().num_days_from_monday());
+            Some((When::Date(today + Days::new(days_to_monday)), 2))
+        }
+        ["next", "month", ..] => {
+            let first = today.with_day(1)?.checked_add_months(Months::new(1))?;
+            Some((When::Date(first), 2))
+        }
+        ["next" | "on", day, ..] => parse_day(day, today).map(|when| (when, 2)),
+        [day, ..] => parse_day(day, today).map(|when| (when, 1)),
+        [] => None,
+    }
+}
+
+fn parse_day(word: &str, today: NaiveDate) -> Option<When> {
+    match word {
+        "today" => Some(When::Date(today)),
+        "tonight" => Some(When::Evening(today)),
+        "tomorrow" => Some(When::Date(today.succ_opt()?)),
+        _ => match word.parse::<Weekday>() {
+            // chrono also accepts three-letter names, which are too easily
+            // part of a title ("sun cream"), so require the full name.
+            Ok(weekday) if word.len() > 3 => Some(When::Date(next_weekday(today, weekday))),
+            _ => NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                .ok()
+                .map(When::Date),
+        },
+    }
+}
+
+/// The first `weekday` strictly after `today`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(if ahead == 0 { 7 } else { ahead.into() })
+}
+
+/// Matches a time of day: `9am`, `9:30pm`, `9 pm`, `21:00`, `noon`,
+/// `midnight`, optionally preceded by `at`. A bare hour only counts after `at`.
+fn parse_time(words: &[&str]) -> Option<(NaiveTime, usize)> {
+    let lower: Vec<String> = words.iter().take(3).map(|w| w.to_lowercase()).collect();
+    let lower: Vec<&str> = lower.iter().map(String::as_str).collect();
+
+    let (words, prefix) = match lower.as_slice() {
+        ["at", rest @ ..] => (rest, 1),
+        rest => (rest, 0),
+    };
+
+    match words {
+        ["noon", ..] => Some((NaiveTime::from_hms_opt(12, 0, 0)?, prefix + 1)),
+        ["midnight", ..] => Some((NaiveTime::MIN, prefix + 1)),
+        [clock, meridiem @ ("am" | "pm"), ..] => Some((twelve_hour(clock, meridiem)?, prefix + 2)),
+        [word, ..] => {
+            if let Some(clock) = word.strip_suffix("am") {
+                Some((twelve_hour(clock, "am")?, prefix + 1))
+            } else if let Some(clock) = word.strip_suffix("pm") {
+                Some((twelve_hour(clock, "pm")?, prefix + 1))
+            } else if word.contains(':') || prefix == 1 {
+                Some((twenty_four_hour(word)?, prefix + 1))
+            } else {
+                None
+            }
+        }
+        [] => None,
+    }
+}
+
+fn hour_minute(clock: &str) -> Option<(u32, u32)> {
+    let (hour, minute) = clock.split_once(':').unwrap_or((clock, "0"));
+    if hour.is_empty() || hour.len() > 2 || minute.len() > 2 {
+        return None;
+    }
+    Some((hour.parse().ok()?, minute.parse().ok()?))
+}
+
+fn twelve_hour(clock: &str, meridiem: &str) -> Option<NaiveTime> {
+    let (hour, minute) = hour_minute(clock)?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = match meridiem {
+        "am" => hour % 12,
+        _ => hour % 12 + 12,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn twenty_four_hour(clock: &str) -> Option<NaiveTime> {
+    let (hour, minute) = hour_minute(clock)?;
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Combines the recognised parts into a timestamp. A time without a date
+/// means its next occurrence.
+fn resolve(
+    when: Option<When>,
+    time: Option<NaiveTime>,
+    now: DateTime<FixedOffset>,
+) -> Option<DateTime<FixedOffset>> {
+    let at = |date: NaiveDate, time: NaiveTime| {
+        now.timezone()
+            .from_local_datetime(&date.and_time(time))
+            .single()
+    };
+    let default_time = |(hour, minute)| NaiveTime::from_hms_opt(hour, minute, 0);
+
+    match (when, time) {
+        (Some(When::Exact(exact)), None) => Some(exact),
+        (Some(When::Exact(exact)), Some(time)) => at(exact.date_naive(), time),
+        (Some(When::Date(date)), time) => at(date, time.or(default_time(DEFAULT_DUE_TIME))?),
+        (Some(When::Evening(date)), time) => at(date, time.or(default_time(TONIGHT))?),
+        (None, Some(time)) => {
+            let today = at(now.date_naive(), time)?;
+            if today > now {
+                Some(today)
+            } else {
+                at(now.date_naive().succ_opt()?, time)
+            }
+        }
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 2024-03-06 10:00 UTC.
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2024-03-06T10:00:00+00:00").unwrap()
+    }
+
+    fn utc(value: &str) -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    /// Input, title, due date, priority and tags.
+    type Case = (
+        &'static str,
+        &'static str,
+        Option<DateTime<Utc>>,
+        Option<Priority>,
+        Vec<&'static str>,
+    );
+
+    #[test]
+    fn test_parse_table() {
+        use Priority::*;
+
+        #[rustfmt::skip]
+        let cases: Vec<Case> = vec![
+            ("Pay rent tomorrow 9am #finance !high", "Pay rent", utc("2024-03-07T09:00:00Z"), Some(High), vec!["finance"]),
+            ("Buy milk", "Buy milk", None, None, vec![]),
+            ("  Buy   milk  ", "Buy milk", None, None, vec![]),
+            ("", "", None, None, vec![]),
+            // Dates
+            ("Call mom today", "Call mom", utc("2024-03-06T17:00:00Z"), None, vec![]),
+            ("Ship tonight", "Ship", utc("2024-03-06T20:00:00Z"), None, vec![]),
+            ("Ship tonight at 22:15", "Ship", utc("2024-03-06T22:15:00Z"), None, vec![]),
+            ("Dentist tomorrow", "Dentist", utc("2024-03-07T17:00:00Z"), None, vec![]),
+            ("Report friday 3pm", "Report", utc("2024-03-08T15:00:00Z"), None, vec![]),
+            ("Sync wednesday", "Sync", utc("2024-03-13T17:00:00Z"), None, vec![]),
+            ("Sync next monday", "Sync", utc("2024-03-11T17:00:00Z"), None, vec![]),
+            ("Review on Tuesday", "Review", utc("2024-03-12T17:00:00Z"), None, vec![]),
+            ("Plan next week", "Plan", utc("2024-03-11T17:00:00Z"), None, vec![]),
+            ("Budget next month", "Budget", utc("2024-04-01T17:00:00Z"), None, vec![]),
+            ("Taxes on 2024-04-15", "Taxes", utc("2024-04-15T17:00:00Z"), None, vec![]),
+            ("Taxes 2024-04-15 8am", "Taxes", utc("2024-04-15T08:00:00Z"), None, vec![]),
+            ("Review in 3 days", "Review", utc("2024-03-09T17:00:00Z"), None, vec![]),
+            ("Review in 1 day at 9", "Review", utc("2024-03-07T09:00:00Z"), None, vec![]),
+            ("Renew in 2 weeks", "Renew", utc("2024-03-20T17:00:00Z"), None, vec![]),
+            ("Renew in 1 month", "Renew", utc("2024-04-06T17:00:00Z"), None, vec![]),
+            ("Deploy in 2 hours", "Deploy", utc("2024-03-06T12:00:00Z"), None, vec![]),
+            ("Deploy in 45 mins", "Deploy", utc("2024-03-06T10:45:00Z"), None, vec![]),
+            ("Dinner tomorrow tomorrow", "Dinner tomorrow", utc("2024-03-07T17:00:00Z"), None, vec![]),
+            ("Case TOMORROW 9AM", "Case", utc("2024-03-07T09:00:00Z"), None, vec![]),
+            // Times
+            ("Lunch at noon", "Lunch", utc("2024-03-06T12:00:00Z"), None, vec![]),
+            ("Standup at 9:30", "Standup", utc("2024-03-07T09:30:00Z"), None, vec![]),
+            ("Standup 10:30", "Standup", utc("2024-03-06T10:30:00Z"), None, vec![]),
+            ("Call 9 pm tomorrow", "Call", utc("2024-03-07T21:00:00Z"), None, vec![]),
+            ("Party 12am", "Party", utc("2024-03-07T00:00:00Z"), None, vec![]),
+            ("Lunch 12pm", "Lunch", utc("2024-03-06T12:00:00Z"), None, vec![]),
+            ("Reset at midnight", "Reset", utc("2024-03-07T00:00:00Z"), None, vec![]),
+            ("Call 9am 5pm", "Call 5pm", utc("2024-03-07T09:00:00Z"), None, vec![]),
+            // Words that only look like dates or times
+            ("Buy 3 apples", "Buy 3 apples", None, None, vec![]),
+            ("Buy sun cream", "Buy sun cream", None, None, vec![]),
+            ("Invalid 13pm", "Invalid 13pm", None, None, vec![]),
+            ("Invalid 25:00", "Invalid 25:00", None, None, vec![]),
+            ("Meet at the office", "Meet at the office", None, None, vec![]),
+            ("Check in on Bob", "Check in on Bob", None, None, vec![]),
+            ("Weekly next steps", "Weekly next steps", None, None, vec![]),
+            ("Wait in 4294967295 hours", "Wait in 4294967295 hours", None, None, vec![]),
+            // Tags and priorities
+            ("Taxes #money #Money #tax-2024", "Taxes", None, None, vec!["money", "tax-2024"]),
+            ("Learn C# basics", "Learn C# basics", None, None, vec![]),
+            ("Lone # sign", "Lone # sign", None, None, vec![]),
+            ("Fix #bug in 30 minutes !low", "Fix", utc("2024-03-06T10:30:00Z"), Some(Low), vec!["bug"]),
+            ("Task !med", "Task", None, Some(Medium), vec![]),
+            ("Task !URGENT", "Task", None, Some(Urgent), vec![]),
+            ("Task !high !low", "Task", None, Some(Low), vec![]),
+            ("Ask !soon", "Ask !soon", None, None, vec![]),
+            ("Wow!", "Wow!", None, None, vec![]),
+            ("#finance !high", "", None, Some(High), vec!["finance"]),
+        ];
+
+        for (text, title, due_at, priority, tags) in cases {
+            let parsed = parse(text, now());
+
+            assert_eq!(parsed.title, title, "title of {text:?}");
+            assert_eq!(parsed.due_at, due_at, "due_at of {text:?}");
+            assert_eq!(parsed.priority, priority, "priority of {text:?}");
+            assert_eq!(parsed.tags, tags, "tags of {text:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_uses_offset_of_now() {
+        let now = DateTime::parse_from_rfc3339("2024-03-06T23:30:00+02:00").unwrap();
+
+        let parsed = parse("Pay rent tomorrow 9am", now);
+
+        assert_eq!(parsed.due_at, utc("2024-03-07T07:00:00Z"));
+    }
+
+    #[test]
+    fn test_next_weekday_is_strictly_after_today() {
+        let wednesday = NaiveDate::from_ymd_opt(2024, 3, 6).unwrap();
+
+        assert_eq!(
+            next_weekday(wednesday, Weekday::Thu),
+            NaiveDate::from_ymd_opt(2024, 3, 7).unwrap()
+        );
+        assert_eq!(
+            next_weekday(wednesday, Weekday::Wed),
+            NaiveDate::from_ymd_opt(2024, 3, 13).unwrap()
+        );
+        assert_eq!(
+            next_weekday(wednesday, Weekday::Mon),
+            NaiveDate::from_ymd_opt(2024, 3, 11).unwrap()
+        );
+    }
+}
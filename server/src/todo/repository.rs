@@ -1,17 +1,204 @@
-use chrono::{DateTime, Utc};
-use entity::todo::{ActiveModel, Entity, Model};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, Set, TryIntoModel};
+use chrono::{DateTime, FixedOffset, Utc};
+use entity::sea_orm_active_enums::{TodoPriority, TodoStatus};
+use entity::todo::{ActiveModel, Column, Entity, Model};
+use entity::todo_comment::{
+    ActiveModel as CommentActiveModel, Column as CommentColumn, Entity as CommentEntity,
+    Model as CommentModel,
+};
+use entity::todo_dependency::{
+    ActiveModel as DependencyActiveModel, Column as DependencyColumn, Entity as DependencyEntity,
+    Model as DependencyModel,
+};
+use entity::todo_tag::{
+    ActiveModel as TagActiveModel, Column as TagColumn, Entity as TagEntity, Model as TagModel,
+};
+use entity::todo_tombstone::{
+    Column as TombstoneColumn, Entity as TombstoneEntity, Model as TombstoneModel,
+};
+use sea_orm::sea_query::{Expr, LockBehavior, LockType, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IsolationLevel, NotSet, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    Statement, TransactionTrait, TryIntoModel,
+};
+use serde_json::json;
+use std::collections::HashSet;
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 use uuid::Uuid;
 
 use async_trait::async_trait;
 
+use crate::common::error::database::is_serialization_failure;
+use crate::notification::{self, model::NewNotification};
+use crate::outbox::{self, model::NewOutboxEvent};
+
+use super::model::TodoResponse;
+use super::status;
+
 #[async_trait]
 pub trait TodoRepository: Send + Sync {
     async fn get_by_id(&self, id: Uuid) -> Result<Model, DbErr>;
-    async fn get_all(&self) -> Result<Vec<Model>, DbErr>;
-    async fn create(&self, title: String) -> Result<Model, DbErr>;
-    async fn update(&self, id: Uuid, title: String, completed: bool) -> Result<Model, DbErr>;
+    /// Looks up a todo by its number among `owner_id`'s todos, or among
+    /// todos without an owner.
+    async fn get_by_number(&self, owner_id: Option<String>, number: i32) -> Result<Model, DbErr>;
+    async fn get_all(&self, filter: &TodoFilter) -> Result<Vec<Model>, DbErr>;
+    /// Same rows as [`Self::get_all`], read from a database stream as the
+    /// receiver consumes them instead of being collected up front.
+    fn stream_all(&self, filter: &TodoFilter) -> ReceiverStream<Result<Model, DbErr>>;
+    /// A window of [`Self::get_all`]; without a limit it reads to the end.
+    async fn get_page(
+        &self,
+        filter: &TodoFilter,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<Vec<Model>, DbErr>;
+    async fn count(&self, filter: &TodoFilter) -> Result<u64, DbErr>;
+    async fn create(&self, todo: NewTodo) -> Result<Model, DbErr>;
+    async fn update(&self, id: Uuid, changes: TodoChanges) -> Result<Model, DbErr>;
     async fn delete(&self, id: Uuid) -> Result<(), DbErr>;
+    /// Changes to `owner_id`'s todos after the `since` token.
+    async fn changes_since(
+        &self,
+        owner_id: &str,
+        since: i64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, Vec<TombstoneModel>), DbErr>;
+    /// Applies a client change to a todo of `owner_id`, creating it for them
+    /// if it does not exist. Another owner's todo is reported as not found.
+    async fn upsert_if_newer(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        title: String,
+        status: Option<TodoStatus>,
+        completed: Option<bool>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<SyncOutcome, DbErr>;
+    async fn delete_if_newer(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<SyncOutcome, DbErr>;
+    async fn archive_completed(&self, before: DateTime<Utc>, limit: u64) -> Result<u64, DbErr>;
+    async fn unarchive(&self, id: Uuid) -> Result<Model, DbErr>;
+    async fn get_blockers(&self, id: Uuid) -> Result<Vec<Model>, DbErr>;
+    /// Records that `todo_id` is blocked by `blocked_by_id`. Returns `false`
+    /// without adding it when `blocked_by_id` already depends on `todo_id`.
+    async fn add_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> Result<bool, DbErr>;
+    async fn remove_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> Result<(), DbErr>;
+    async fn get_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<(Vec<Model>, Vec<DependencyModel>), DbErr>;
+    /// Only the owner or the current assignee may change the assignee.
+    async fn assign(
+        &self,
+        id: Uuid,
+        assignee_id: Option<String>,
+        actor_id: String,
+    ) -> Result<Model, DbErr>;
+    async fn add_comment(
+        &self,
+        todo_id: Uuid,
+        author_id: String,
+        body: String,
+    ) -> Result<CommentModel, DbErr>;
+    async fn list_comments(&self, todo_id: Uuid) -> Result<Vec<CommentModel>, DbErr>;
+    async fn create_with_subtasks(
+        &self,
+        parent: NewTodo,
+        subtasks: Vec<String>,
+    ) -> Result<(Model, Vec<Model>), DbErr>;
+    async fn get_subtasks(&self, parent_id: Uuid) -> Result<Vec<Model>, DbErr>;
+    async fn get_tags_of(&self, ids: Vec<Uuid>) -> Result<Vec<TagModel>, DbErr>;
+    async fn get_subtasks_of(&self, parent_ids: Vec<Uuid>) -> Result<Vec<Model>, DbErr>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewTodo {
+    pub title: String,
+    pub description: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub owner_id: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<TodoPriority>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+}
+
+/// Full replacement of the editable fields of a todo.
+#[derive(Debug, Clone)]
+pub struct TodoChanges {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TodoStatus,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<TodoPriority>,
+    pub remind_at: Option<DateTime<Utc>>,
+    /// Clears the sent marker so a moved reminder is sent again.
+    pub rearm_reminder: bool,
+    /// Replaces the todo's tags when set.
+    pub tags: Option<Vec<String>>,
+    /// The status the change was validated against. If another write changed
+    /// it meanwhile, nothing is updated and `RecordNotUpdated` is returned.
+    pub expected_status: TodoStatus,
+}
+
+/// Criteria shared by every todo listing. Empty lists match everything.
+#[derive(Debug, Clone, Default)]
+pub struct TodoFilter {
+    pub archived: bool,
+    pub statuses: Vec<TodoStatus>,
+    pub priorities: Vec<TodoPriority>,
+    pub tags: Vec<String>,
+    pub project_id: Option<Uuid>,
+    pub due_after: Option<DateTime<Utc>>,
+    pub due_before: Option<DateTime<Utc>>,
+}
+
+impl TodoFilter {
+    fn condition(&self) -> Condition {
+        let archived = if self.archived {
+            Column::ArchivedAt.is_not_null()
+        } else {
+            Column::ArchivedAt.is_null()
+        };
+
+        Condition::all()
+            .add(archived)
+            .add_option(
+                (!self.statuses.is_empty()).then(|| Column::Status.is_in(self.statuses.clone())),
+            )
+            .add_option(
+                (!self.priorities.is_empty())
+                    .then(|| Column::Priority.is_in(self.priorities.clone())),
+            )
+            .add_option((!self.tags.is_empty()).then(|| {
+                Column::Id.in_subquery(
+                    Query::select()
+                        .column(TagColumn::TodoId)
+                        .from(TagEntity)
+                        .and_where(TagColumn::Tag.is_in(self.tags.clone()))
+                        .to_owned(),
+                )
+            }))
+            .add_option(self.project_id.map(|id| Column::ProjectId.eq(id)))
+            .add_option(self.due_after.map(|at| Column::DueAt.gte(at)))
+            .add_option(self.due_before.map(|at| Column::DueAt.lt(at)))
+    }
+}
+
+/// Result of applying a client change under last-writer-wins. Both variants
+/// carry the server state after the operation.
+#[derive(Debug)]
+pub enum SyncOutcome {
+    Applied(Option<Model>),
+    Stale(Option<Model>),
+    /// The change would complete a todo that still has open blockers.
+    Blocked(Option<Model>),
 }
 
 #[derive(Clone)]
@@ -28,38 +215,1088 @@ impl TodoRepository for TodoRepositoryImpl {
             .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))
     }
 
-    async fn get_all(&self) -> Result<Vec<Model>, DbErr> {
-        Entity::find().all(&self.db).await
+    async fn get_by_number(&self, owner_id: Option<String>, number: i32) -> Result<Model, DbErr> {
+        let owner = match owner_id {
+            Some(owner_id) => Column::OwnerId.eq(owner_id),
+            None => Column::OwnerId.is_null(),
+        };
+
+        Entity::find()
+            .filter(owner)
+            .filter(Column::Number.eq(number))
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))
+    }
+
+    async fn get_all(&self, filter: &TodoFilter) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(filter.condition())
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    fn stream_all(&self, filter: &TodoFilter) -> ReceiverStream<Result<Model, DbErr>> {
+        let db = self.db.clone();
+        let query = Entity::find()
+            .filter(filter.condition())
+            .order_by_asc(Column::CreatedAt);
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        // The row stream borrows the connection, so it is driven from its own
+        // task. The bounded channel stops reading rows while the receiver lags.
+        tokio::spawn(async move {
+            let rows = match query.stream(&db).await {
+                Ok(rows) => rows,
+                Err(err) => {
+                    let _ = tx.send(Err(err)).await;
+                    return;
+                }
+            };
+            tokio::pin!(rows);
+
+            while let Some(row) = rows.next().await {
+                if tx.send(row).await.is_err() {
+                    // The receiver was dropped, e.g. the client disconnected.
+                    break;
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
     }
 
-    async fn create(&self, title: String) -> Result<Model, DbErr> {
-        let new_todo = ActiveModel {
-            title: Set(title),
+    async fn get_page(
+        &self,
+        filter: &TodoFilter,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(filter.condition())
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .offset(offset)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count(&self, filter: &TodoFilter) -> Result<u64, DbErr> {
+        Entity::find()
+            .filter(filter.condition())
+            .count(&self.db)
+            .await
+    }
+
+    async fn create(&self, todo: NewTodo) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+        let new_todo = insert_todo(&txn, todo, None).await?;
+        txn.commit().await?;
+
+        Ok(new_todo)
+    }
+
+    async fn update(&self, id: Uuid, changes: TodoChanges) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let mut updated = Entity::update_many()
+            .set(ActiveModel {
+                title: Set(changes.title),
+                description: Set(changes.description),
+                status: Set(changes.status),
+                project_id: Set(changes.project_id),
+                due_at: Set(changes.due_at.map(Into::into)),
+                priority: Set(changes.priority),
+                remind_at: Set(changes.remind_at.map(Into::into)),
+                reminded_at: if changes.rearm_reminder {
+                    Set(None)
+                } else {
+                    NotSet
+                },
+                updated_at: Set(DateTime::from(Utc::now())),
+                ..Default::default()
+            })
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(changes.expected_status))
+            .exec_with_returning(&txn)
+            .await?;
+        let updated_todo = updated.pop().ok_or(DbErr::RecordNotUpdated)?;
+
+        if let Some(tags) = changes.tags {
+            TagEntity::delete_many()
+                .filter(TagColumn::TodoId.eq(id))
+                .exec(&txn)
+                .await?;
+            if !tags.is_empty() {
+                TagEntity::insert_many(tags.into_iter().map(|tag| TagActiveModel {
+                    todo_id: Set(id),
+                    tag: Set(tag),
+                }))
+                .exec_without_returning(&txn)
+                .await?;
+            }
+        }
+
+        outbox::repository::insert(&txn, todo_event(TODO_UPDATED, &updated_todo)).await?;
+        txn.commit().await?;
+
+        Ok(updated_todo)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+
+        let res = Entity::delete_by_id(id).exec(&txn).await?;
+        if res.rows_affected > 0 {
+            outbox::repository::insert(&txn, todo_deleted_event(id)).await?;
+        }
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn changes_since(
+        &self,
+        owner_id: &str,
+        since: i64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, Vec<TombstoneModel>), DbErr> {
+        // Changes above the watermark may sit next to lower ones that are not
+        // committed yet; returning them would move the client's token past
+        // those and lose them.
+        let watermark: i64 = self
+            .db
+            .query_one(Statement::from_string(
+                self.db.get_database_backend(),
+                "SELECT todo_change_watermark() AS watermark",
+            ))
+            .await?
+            .ok_or_else(|| DbErr::Custom("no change watermark".into()))?
+            .try_get("", "watermark")?;
+
+        let todos = Entity::find()
+            .filter(Column::OwnerId.eq(owner_id))
+            .filter(Column::ChangeSeq.gt(since))
+            .filter(Column::ChangeSeq.lte(watermark))
+            .order_by_asc(Column::ChangeSeq)
+            .limit(limit)
+            .all(&self.db)
+            .await?;
+
+        let tombstones = TombstoneEntity::find()
+            .filter(TombstoneColumn::OwnerId.eq(owner_id))
+            .filter(TombstoneColumn::ChangeSeq.gt(since))
+            .filter(TombstoneColumn::ChangeSeq.lte(watermark))
+            .order_by_asc(TombstoneColumn::ChangeSeq)
+            .limit(limit)
+            .all(&self.db)
+            .await?;
+
+        Ok((todos, tombstones))
+    }
+
+    async fn upsert_if_newer(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        title: String,
+        status: Option<TodoStatus>,
+        completed: Option<bool>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<SyncOutcome, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let existing = Entity::find_by_id(id).lock_exclusive().one(&txn).await?;
+        let outcome = match existing {
+            Some(todo) if todo.owner_id.as_deref() != Some(owner_id) => {
+                return Err(DbErr::RecordNotFound("Todo not found".to_string()));
+            }
+            Some(todo) if !client_wins(updated_at, todo.updated_at) => {
+                SyncOutcome::Stale(Some(todo))
+            }
+            Some(todo)
+                if status::resolve(status, completed, todo.status) == TodoStatus::Done
+                    && todo.status != TodoStatus::Done
+                    && has_open_blockers(&txn, id).await? =>
+            {
+                SyncOutcome::Blocked(Some(todo))
+            }
+            Some(todo) => {
+                let todo = ActiveModel {
+                    id: Set(id),
+                    title: Set(title),
+                    status: Set(status::resolve(status, completed, todo.status)),
+                    updated_at: Set(updated_at.into()),
+                    ..Default::default()
+                }
+                .update(&txn)
+                .await?;
+
+                outbox::repository::insert(&txn, todo_event(TODO_UPDATED, &todo)).await?;
+                SyncOutcome::Applied(Some(todo))
+            }
+            None => {
+                let tombstone = TombstoneEntity::find_by_id(id).one(&txn).await?;
+                match tombstone {
+                    Some(t) if t.owner_id.as_deref() != Some(owner_id) => {
+                        return Err(DbErr::RecordNotFound("Todo not found".to_string()));
+                    }
+                    Some(t) if !client_wins(updated_at, t.deleted_at) => SyncOutcome::Stale(None),
+                    _ => {
+                        let todo = ActiveModel {
+                            id: Set(id),
+                            title: Set(title),
+                            status: Set(status::resolve(status, completed, TodoStatus::Todo)),
+                            updated_at: Set(updated_at.into()),
+                            owner_id: Set(Some(owner_id.to_string())),
+                            ..Default::default()
+                        }
+                        .insert(&txn)
+                        .await?;
+
+                        outbox::repository::insert(&txn, todo_event(TODO_CREATED, &todo)).await?;
+                        SyncOutcome::Applied(Some(todo))
+                    }
+                }
+            }
+        };
+
+        txn.commit().await?;
+        Ok(outcome)
+    }
+
+    async fn delete_if_newer(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<SyncOutcome, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let existing = Entity::find_by_id(id).lock_exclusive().one(&txn).await?;
+        let outcome = match existing {
+            Some(todo) if todo.owner_id.as_deref() != Some(owner_id) => {
+                return Err(DbErr::RecordNotFound("Todo not found".to_string()));
+            }
+            Some(todo) if !client_wins(deleted_at, todo.updated_at) => {
+                SyncOutcome::Stale(Some(todo))
+            }
+            Some(_) => {
+                Entity::delete_by_id(id).exec(&txn).await?;
+                outbox::repository::insert(&txn, todo_deleted_event(id)).await?;
+                SyncOutcome::Applied(None)
+            }
+            None => SyncOutcome::Applied(None),
+        };
+
+        txn.commit().await?;
+        Ok(outcome)
+    }
+
+    async fn archive_completed(&self, before: DateTime<Utc>, limit: u64) -> Result<u64, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let ids: Vec<Uuid> = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::Status.eq(TodoStatus::Done))
+            .filter(Column::ArchivedAt.is_null())
+            .filter(Column::CompletedAt.lt(before))
+            // Unarchiving touches `updated_at`, which keeps the todo out of
+            // the sweep for another retention period.
+            .filter(Column::UpdatedAt.lt(before))
+            .order_by_asc(Column::CompletedAt)
+            .limit(limit)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .into_tuple()
+            .all(&txn)
+            .await?;
+
+        if ids.is_empty() {
+            txn.commit().await?;
+            return Ok(0);
+        }
+
+        let archived = Entity::update_many()
+            .col_expr(Column::ArchivedAt, Expr::value(Utc::now()))
+            .filter(Column::Id.is_in(ids))
+            .exec_with_returning(&txn)
+            .await?;
+
+        for todo in &archived {
+            outbox::repository::insert(&txn, todo_event(TODO_ARCHIVED, todo)).await?;
+        }
+        txn.commit().await?;
+
+        Ok(archived.len() as u64)
+    }
+
+    async fn unarchive(&self, id: Uuid) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let todo = ActiveModel {
+            id: Set(id),
+            archived_at: Set(None),
+            updated_at: Set(DateTime::from(Utc::now())),
             ..Default::default()
         }
-        .save(&self.db)
+        .update(&txn)
+        .await?;
+
+        outbox::repository::insert(&txn, todo_event(TODO_UNARCHIVED, &todo)).await?;
+        txn.commit().await?;
+
+        Ok(todo)
+    }
+
+    async fn get_blockers(&self, id: Uuid) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(
+                Column::Id.in_subquery(
+                    Query::select()
+                        .column(DependencyColumn::BlockedById)
+                        .from(DependencyEntity)
+                        .and_where(DependencyColumn::TodoId.eq(id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn add_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> Result<bool, DbErr> {
+        let mut attempt = 1;
+        loop {
+            match try_add_dependency(&self.db, todo_id, blocked_by_id).await {
+                Err(err) if attempt < SERIALIZATION_ATTEMPTS && is_serialization_failure(&err) => {
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn remove_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+
+        let res = DependencyEntity::delete_by_id((todo_id, blocked_by_id))
+            .exec(&txn)
+            .await?;
+        if res.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Dependency not found".to_string()));
+        }
+
+        outbox::repository::insert(
+            &txn,
+            dependency_event(TODO_DEPENDENCY_REMOVED, todo_id, blocked_by_id),
+        )
         .await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_by_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<(Vec<Model>, Vec<DependencyModel>), DbErr> {
+        let todos = Entity::find()
+            .filter(Column::ProjectId.eq(project_id))
+            .filter(Column::ArchivedAt.is_null())
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await?;
 
-        Ok(new_todo.try_into_model()?)
+        let ids: Vec<Uuid> = todos.iter().map(|todo| todo.id).collect();
+        let dependencies = DependencyEntity::find()
+            .filter(DependencyColumn::TodoId.is_in(ids.clone()))
+            .filter(DependencyColumn::BlockedById.is_in(ids))
+            .all(&self.db)
+            .await?;
+
+        Ok((todos, dependencies))
     }
 
-    async fn update(&self, id: Uuid, title: String, completed: bool) -> Result<Model, DbErr> {
-        let updated_todo = ActiveModel {
+    async fn assign(
+        &self,
+        id: Uuid,
+        assignee_id: Option<String>,
+        actor_id: String,
+    ) -> Result<Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let current = Entity::find_by_id(id)
+            .filter(
+                Condition::any()
+                    .add(Column::OwnerId.eq(actor_id.as_str()))
+                    .add(Column::AssigneeId.eq(actor_id.as_str())),
+            )
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))?;
+        if current.assignee_id == assignee_id {
+            return Ok(current);
+        }
+
+        let todo = ActiveModel {
             id: Set(id),
-            title: Set(title),
-            completed: Set(completed),
+            assignee_id: Set(assignee_id),
             updated_at: Set(DateTime::from(Utc::now())),
             ..Default::default()
         }
-        .update(&self.db)
+        .update(&txn)
         .await?;
 
-        Ok(updated_todo.try_into_model()?)
+        let event_type = if todo.assignee_id.is_some() {
+            TODO_ASSIGNED
+        } else {
+            TODO_UNASSIGNED
+        };
+        outbox::repository::insert(&txn, todo_event(event_type, &todo)).await?;
+        notification::repository::insert_many(
+            &txn,
+            NewNotification::assigned(&todo, Some(&actor_id))
+                .into_iter()
+                .collect(),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(todo)
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), DbErr> {
-        Entity::delete_by_id(id).exec(&self.db).await?;
+    async fn add_comment(
+        &self,
+        todo_id: Uuid,
+        author_id: String,
+        body: String,
+    ) -> Result<CommentModel, DbErr> {
+        let txn = self.db.begin().await?;
 
-        Ok(())
+        let todo = Entity::find_by_id(todo_id)
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Todo not found".to_string()))?;
+
+        let comment = CommentEntity::insert(CommentActiveModel {
+            todo_id: Set(todo_id),
+            author_id: Set(author_id),
+            body: Set(body),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await?;
+
+        outbox::repository::insert(&txn, comment_event(&comment)).await?;
+        notification::repository::insert_many(
+            &txn,
+            NewNotification::commented(&todo, &comment.author_id)
+                .into_iter()
+                .collect(),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn list_comments(&self, todo_id: Uuid) -> Result<Vec<CommentModel>, DbErr> {
+        CommentEntity::find()
+            .filter(CommentColumn::TodoId.eq(todo_id))
+            .order_by_asc(CommentColumn::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn create_with_subtasks(
+        &self,
+        parent: NewTodo,
+        subtasks: Vec<String>,
+    ) -> Result<(Model, Vec<Model>), DbErr> {
+        let txn = self.db.begin().await?;
+
+        let owner_id = parent.owner_id.clone();
+        let project_id = parent.project_id;
+        let parent = insert_todo(&txn, parent, None).await?;
+
+        let mut children = Vec::with_capacity(subtasks.len());
+        for (position, title) in (0..).zip(subtasks) {
+            let subtask = NewTodo {
+                title,
+                project_id,
+                owner_id: owner_id.clone(),
+                ..Default::default()
+            };
+            children.push(insert_todo(&txn, subtask, Some((parent.id, position))).await?);
+        }
+
+        txn.commit().await?;
+        Ok((parent, children))
+    }
+
+    async fn get_subtasks(&self, parent_id: Uuid) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::ParentId.eq(parent_id))
+            .order_by_asc(Column::Position)
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get_tags_of(&self, ids: Vec<Uuid>) -> Result<Vec<TagModel>, DbErr> {
+        TagEntity::find()
+            .filter(TagColumn::TodoId.is_in(ids))
+            .order_by_asc(TagColumn::Tag)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get_subtasks_of(&self, parent_ids: Vec<Uuid>) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::ParentId.is_in(parent_ids))
+            .order_by_asc(Column::Position)
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+}
+
+/// Inserts a todo with its tags and outbox event on the caller's
+/// transaction; `parent` places it as a subtask at the given position.
+async fn insert_todo<C: ConnectionTrait>(
+    conn: &C,
+    todo: NewTodo,
+    parent: Option<(Uuid, i32)>,
+) -> Result<Model, DbErr> {
+    let new_todo = ActiveModel {
+        title: Set(todo.title),
+        description: Set(todo.description),
+        project_id: Set(todo.project_id),
+        owner_id: Set(todo.owner_id),
+        due_at: Set(todo.due_at.map(Into::into)),
+        priority: Set(todo.priority),
+        remind_at: Set(todo.remind_at.map(Into::into)),
+        parent_id: Set(parent.map(|(parent_id, _)| parent_id)),
+        position: Set(parent.map(|(_, position)| position)),
+        ..Default::default()
+    }
+    .save(conn)
+    .await?
+    .try_into_model()?;
+
+    if !todo.tags.is_empty() {
+        TagEntity::insert_many(todo.tags.into_iter().map(|tag| TagActiveModel {
+            todo_id: Set(new_todo.id),
+            tag: Set(tag),
+        }))
+        .exec_without_returning(conn)
+        .await?;
+    }
+
+    outbox::repository::insert(conn, todo_event(TODO_CREATED, &new_todo)).await?;
+    Ok(new_todo)
+}
+
+/// Attempts at a dependency insert before a serialization failure is returned.
+const SERIALIZATION_ATTEMPTS: u32 = 3;
+
+/// Checks for a cycle and inserts in one serializable transaction, so two
+/// concurrent inserts cannot close a cycle that neither saw on its own.
+async fn try_add_dependency(
+    db: &DatabaseConnection,
+    todo_id: Uuid,
+    blocked_by_id: Uuid,
+) -> Result<bool, DbErr> {
+    let txn = db
+        .begin_with_config(Some(IsolationLevel::Serializable), None)
+        .await?;
+
+    if depends_on(&txn, blocked_by_id, todo_id).await? {
+        txn.rollback().await?;
+        return Ok(false);
+    }
+
+    DependencyEntity::insert(DependencyActiveModel {
+        todo_id: Set(todo_id),
+        blocked_by_id: Set(blocked_by_id),
+        ..Default::default()
+    })
+    .exec_without_returning(&txn)
+    .await?;
+
+    outbox::repository::insert(
+        &txn,
+        dependency_event(TODO_DEPENDENCY_ADDED, todo_id, blocked_by_id),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+/// Walks the blockers of `from` breadth-first, one query per level,
+/// and reports whether `target` is among them.
+async fn depends_on<C: ConnectionTrait>(conn: &C, from: Uuid, target: Uuid) -> Result<bool, DbErr> {
+    let mut seen = HashSet::from([from]);
+    let mut frontier = vec![from];
+
+    while !frontier.is_empty() {
+        if frontier.contains(&target) {
+            return Ok(true);
+        }
+
+        let edges = DependencyEntity::find()
+            .filter(DependencyColumn::TodoId.is_in(frontier))
+            .all(conn)
+            .await?;
+        frontier = edges
+            .into_iter()
+            .map(|edge| edge.blocked_by_id)
+            .filter(|id| seen.insert(*id))
+            .collect();
+    }
+
+    Ok(false)
+}
+
+/// Whether `id` is blocked by a todo that is neither done nor cancelled.
+async fn has_open_blockers<C: ConnectionTrait>(conn: &C, id: Uuid) -> Result<bool, DbErr> {
+    let open = Entity::find()
+        .filter(
+            Column::Id.in_subquery(
+                Query::select()
+                    .column(DependencyColumn::BlockedById)
+                    .from(DependencyEntity)
+                    .and_where(DependencyColumn::TodoId.eq(id))
+                    .to_owned(),
+            ),
+        )
+        .filter(Column::Status.is_not_in([TodoStatus::Done, TodoStatus::Cancelled]))
+        .count(conn)
+        .await?;
+
+    Ok(open > 0)
+}
+
+/// Last-writer-wins: the client change is applied only if it is strictly
+/// newer than the server state, so replaying a push is a no-op.
+fn client_wins(client: DateTime<Utc>, server: DateTime<FixedOffset>) -> bool {
+    client > server
+}
+
+/// Rows [`TodoRepository::stream_all`] reads ahead of its receiver.
+const STREAM_BUFFER: usize = 64;
+
+const TODO_AGGREGATE: &str = "todo";
+const TODO_CREATED: &str = "todo.created";
+const TODO_UPDATED: &str = "todo.updated";
+const TODO_DELETED: &str = "todo.deleted";
+const TODO_ARCHIVED: &str = "todo.archived";
+const TODO_UNARCHIVED: &str = "todo.unarchived";
+const TODO_DEPENDENCY_ADDED: &str = "todo.dependency_added";
+const TODO_DEPENDENCY_REMOVED: &str = "todo.dependency_removed";
+const TODO_ASSIGNED: &str = "todo.assigned";
+const TODO_UNASSIGNED: &str = "todo.unassigned";
+const TODO_COMMENTED: &str = "todo.commented";
+
+fn todo_event(event_type: &'static str, todo: &Model) -> NewOutboxEvent {
+    NewOutboxEvent {
+        aggregate_type: TODO_AGGREGATE,
+        aggregate_id: todo.id,
+        event_type,
+        payload: json!(TodoResponse::from(todo.clone())),
+    }
+}
+
+fn dependency_event(
+    event_type: &'static str,
+    todo_id: Uuid,
+    blocked_by_id: Uuid,
+) -> NewOutboxEvent {
+    NewOutboxEvent {
+        aggregate_type: TODO_AGGREGATE,
+        aggregate_id: todo_id,
+        event_type,
+        payload: json!({ "todo_id": todo_id, "blocked_by_id": blocked_by_id }),
+    }
+}
+
+fn comment_event(comment: &CommentModel) -> NewOutboxEvent {
+    NewOutboxEvent {
+        aggregate_type: TODO_AGGREGATE,
+        aggregate_id: comment.todo_id,
+        event_type: TODO_COMMENTED,
+        payload: json!({
+            "todo_id": comment.todo_id,
+            "comment_id": comment.id,
+            "author_id": comment.author_id,
+        }),
+    }
+}
+
+fn todo_deleted_event(id: Uuid) -> NewOutboxEvent {
+    NewOutboxEvent {
+        aggregate_type: TODO_AGGREGATE,
+        aggregate_id: id,
+        event_type: TODO_DELETED,
+        payload: json!({ "id": id }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_db;
+    use chrono::Duration;
+
+    #[test]
+    fn test_client_wins_when_newer() {
+        let server = Utc::now();
+        let client = server + Duration::seconds(1);
+
+        assert!(client_wins(client, server.fixed_offset()));
+    }
+
+    #[test]
+    fn test_server_wins_when_newer_or_equal() {
+        let server = Utc::now();
+
+        assert!(!client_wins(server, server.fixed_offset()));
+        assert!(!client_wins(
+            server - Duration::seconds(1),
+            server.fixed_offset()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_waits_for_earlier_uncommitted_changes() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db: db.clone() };
+        // Separate owners, so the inserts do not share a number counter row.
+        let todo = |title: &str| NewTodo {
+            title: title.to_string(),
+            owner_id: Some(title.to_string()),
+            ..Default::default()
+        };
+
+        // The first writer draws the lower sequence value but commits last.
+        let slow = db.begin().await.unwrap();
+        let first = insert_todo(&slow, todo("first"), None).await.unwrap();
+        let second = repo.create(todo("second")).await.unwrap();
+        assert!(first.change_seq < second.change_seq);
+
+        let pull = tokio::spawn(async move { repo.changes_since("first", 0, 10).await });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!pull.is_finished());
+
+        slow.commit().await.unwrap();
+        let (todos, _) = pull.await.unwrap().unwrap();
+        let ids: Vec<Uuid> = todos.iter().map(|todo| todo.id).collect();
+        assert_eq!(ids, vec![first.id]);
+    }
+
+    #[tokio::test]
+    async fn test_unowned_todos_are_numbered_without_waiting() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db: db.clone() };
+        let todo = |title: &str| NewTodo {
+            title: title.to_string(),
+            ..Default::default()
+        };
+
+        // An open insert must not hold up the next owner-less one.
+        let slow = db.begin().await.unwrap();
+        let first = insert_todo(&slow, todo("first"), None).await.unwrap();
+        let second = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            repo.create(todo("second")),
+        )
+        .await
+        .expect("insert waited for the open transaction")
+        .unwrap();
+        slow.commit().await.unwrap();
+
+        assert!(first.number < second.number);
+        let found = repo.get_by_number(None, second.number).await.unwrap();
+        assert_eq!(found.id, second.id);
+    }
+
+    #[tokio::test]
+    async fn test_unarchived_todo_is_not_archived_again() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db: db.clone() };
+        let todo = repo
+            .create(NewTodo {
+                title: "Done long ago".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let long_ago = Utc::now() - Duration::days(60);
+        Entity::update_many()
+            .set(ActiveModel {
+                status: Set(TodoStatus::Done),
+                completed_at: Set(Some(long_ago.into())),
+                updated_at: Set(long_ago.into()),
+                ..Default::default()
+            })
+            .filter(Column::Id.eq(todo.id))
+            .exec(&db)
+            .await
+            .unwrap();
+
+        let cutoff = Utc::now() - Duration::days(30);
+        assert_eq!(repo.archive_completed(cutoff, 10).await.unwrap(), 1);
+
+        let restored = repo.unarchive(todo.id).await.unwrap();
+        assert!(restored.archived_at.is_none());
+        assert_eq!(repo.archive_completed(cutoff, 10).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_skips_when_status_changed_meanwhile() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let todo = repo
+            .create(NewTodo {
+                title: "Contended".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let changes = |status, expected_status| TodoChanges {
+            title: todo.title.clone(),
+            description: None,
+            status,
+            project_id: None,
+            due_at: None,
+            priority: None,
+            remind_at: None,
+            rearm_reminder: false,
+            tags: None,
+            expected_status,
+        };
+
+        let started = repo
+            .update(todo.id, changes(TodoStatus::InProgress, TodoStatus::Todo))
+            .await
+            .unwrap();
+        assert_eq!(started.status, TodoStatus::InProgress);
+
+        // A second request that also read `todo` loses.
+        let stale = repo
+            .update(todo.id, changes(TodoStatus::Done, TodoStatus::Todo))
+            .await;
+        assert!(matches!(stale, Err(DbErr::RecordNotUpdated)));
+    }
+
+    #[tokio::test]
+    async fn test_only_owner_or_assignee_may_assign() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let todo = repo
+            .create(NewTodo {
+                title: "Delegated".to_string(),
+                owner_id: Some("alice".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let foreign = repo
+            .assign(todo.id, Some("mallory".to_string()), "mallory".to_string())
+            .await;
+        assert!(matches!(foreign, Err(DbErr::RecordNotFound(_))));
+
+        let assigned = repo
+            .assign(todo.id, Some("bob".to_string()), "alice".to_string())
+            .await
+            .unwrap();
+        assert_eq!(assigned.assignee_id.as_deref(), Some("bob"));
+
+        // The assignee may hand it back.
+        let unassigned = repo.assign(todo.id, None, "bob".to_string()).await.unwrap();
+        assert_eq!(unassigned.assignee_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_replaces_tags_only_when_given() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let todo = repo
+            .create(NewTodo {
+                title: "Tagged".to_string(),
+                tags: vec!["home".to_string(), "urgent".to_string()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let changes = |tags| TodoChanges {
+            title: todo.title.clone(),
+            description: None,
+            status: TodoStatus::Todo,
+            project_id: None,
+            due_at: None,
+            priority: None,
+            remind_at: None,
+            rearm_reminder: false,
+            tags,
+            expected_status: TodoStatus::Todo,
+        };
+        let tags = |repo: &TodoRepositoryImpl| {
+            let db = repo.db.clone();
+            async move {
+                TagEntity::find()
+                    .filter(TagColumn::TodoId.eq(todo.id))
+                    .order_by_asc(TagColumn::Tag)
+                    .all(&db)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|tag| tag.tag)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        repo.update(todo.id, changes(None)).await.unwrap();
+        assert_eq!(tags(&repo).await, ["home", "urgent"]);
+
+        repo.update(todo.id, changes(Some(vec!["work".to_string()])))
+            .await
+            .unwrap();
+        assert_eq!(tags(&repo).await, ["work"]);
+
+        repo.update(todo.id, changes(Some(Vec::new())))
+            .await
+            .unwrap();
+        assert!(tags(&repo).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_dependencies_cannot_close_a_cycle() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let mut ids = Vec::new();
+        for title in ["a", "b", "c", "d"] {
+            let todo = repo
+                .create(NewTodo {
+                    title: title.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            ids.push(todo.id);
+        }
+        let [a, b, c, d] = ids[..] else {
+            unreachable!()
+        };
+        assert!(repo.add_dependency(b, c).await.unwrap());
+        assert!(repo.add_dependency(d, a).await.unwrap());
+
+        // a -> b -> c -> d -> a, closed by two inserts with no shared todo.
+        let (first, second) = tokio::join!(repo.add_dependency(a, b), repo.add_dependency(c, d));
+
+        let added = [first, second]
+            .into_iter()
+            .filter(|res| matches!(res, Ok(true)))
+            .count();
+        assert_eq!(added, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_does_not_complete_blocked_todo() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let todo = |title: &str| NewTodo {
+            title: title.to_string(),
+            owner_id: Some("owner".to_string()),
+            ..Default::default()
+        };
+        let blocked = repo.create(todo("blocked")).await.unwrap();
+        let blocker = repo.create(todo("blocker")).await.unwrap();
+        repo.add_dependency(blocked.id, blocker.id).await.unwrap();
+
+        let later = Utc::now() + Duration::seconds(1);
+        let outcome = repo
+            .upsert_if_newer(
+                "owner",
+                blocked.id,
+                blocked.title.clone(),
+                None,
+                Some(true),
+                later,
+            )
+            .await
+            .unwrap();
+
+        match outcome {
+            SyncOutcome::Blocked(Some(current)) => assert_eq!(current.status, TodoStatus::Todo),
+            other => panic!("expected blocked, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_is_limited_to_the_owner() {
+        let Some(db) = test_db::connect().await else {
+            return;
+        };
+        let repo = TodoRepositoryImpl { db };
+        let theirs = repo
+            .create(NewTodo {
+                title: "theirs".to_string(),
+                owner_id: Some("them".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let later = Utc::now() + Duration::seconds(1);
+
+        let overwrite = repo
+            .upsert_if_newer("me", theirs.id, "mine".to_string(), None, None, later)
+            .await;
+        let delete = repo.delete_if_newer("me", theirs.id, later).await;
+        assert!(matches!(overwrite, Err(DbErr::RecordNotFound(_))));
+        assert!(matches!(delete, Err(DbErr::RecordNotFound(_))));
+
+        let id = Uuid::new_v4();
+        let created = repo
+            .upsert_if_newer("me", id, "new".to_string(), None, None, later)
+            .await
+            .unwrap();
+        assert!(
+            matches!(created, SyncOutcome::Applied(Some(todo)) if todo.owner_id.as_deref() == Some("me"))
+        );
+        repo.delete_if_newer("me", id, later + Duration::seconds(1))
+            .await
+            .unwrap();
+
+        let (mine, my_tombstones) = repo.changes_since("me", 0, 10).await.unwrap();
+        let (their_todos, their_tombstones) = repo.changes_since("them", 0, 10).await.unwrap();
+        assert!(mine.is_empty());
+        assert_eq!(my_tombstones.len(), 1);
+        assert_eq!(my_tombstones[0].id, id);
+        assert_eq!(their_todos.len(), 1);
+        assert_eq!(their_todos[0].title, "theirs");
+        assert!(their_tombstones.is_empty());
     }
 }
@@ -1,19 +1,52 @@
-use axum::{routing::get, Router};
+use axum::middleware;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::{common::state::AppState, todo::controller};
+use crate::{
+    common::negotiate::require_acceptable, common::state::AppState,
+    middleware::auth::is_authenticated, todo::controller,
+};
 
-pub fn init() -> Router<AppState> {
-    let router = Router::new()
-        .route(
-            "/{todo_id}",
-            get(controller::get_by_id)
-                .put(controller::update)
-                .delete(controller::delete),
-        )
-        .route("/", get(controller::get_all).post(controller::create));
+/// Routes are registered through `routes!`, which also collects their
+/// `#[utoipa::path]` operations, so the served spec follows this table.
+pub fn init() -> OpenApiRouter<AppState> {
+    // Assigning, commenting and syncing act on behalf of the caller, so they
+    // need a JWT even while the rest of the router is open.
+    let identified_routes = OpenApiRouter::new()
+        .routes(routes!(controller::sync_pull, controller::sync_push))
+        .routes(routes!(controller::assign, controller::unassign))
+        .routes(routes!(controller::add_comment))
+        .layer(middleware::from_fn(is_authenticated));
+
+    let router = OpenApiRouter::new()
+        .merge(identified_routes)
+        .routes(routes!(
+            controller::get_by_id,
+            controller::update,
+            controller::delete
+        ))
+        .routes(routes!(controller::quick_add))
+        .routes(routes!(controller::unarchive))
+        .routes(routes!(
+            controller::get_dependencies,
+            controller::add_dependency
+        ))
+        .routes(routes!(controller::remove_dependency))
+        .routes(routes!(controller::get_comments))
+        .routes(routes!(controller::get_subtasks))
+        .routes(routes!(controller::get_all, controller::create))
+        .layer(middleware::from_fn(require_acceptable))
+        // Exports are NDJSON whatever `Accept` says.
+        .routes(routes!(controller::export));
 
     // Uncomment to enable JWT authentication for all routes in this router
     // router.layer(middleware::from_fn(is_authenticated));
 
     router
 }
+
+/// Todos grouped by project, mounted under `/api/v1/projects`.
+pub fn init_projects() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(controller::get_project_todos))
+        .layer(middleware::from_fn(require_acceptable))
+}
@@ -1,47 +1,657 @@
+use chrono::{DateTime, Duration, Utc};
+use entity::sea_orm_active_enums::{TodoPriority, TodoStatus};
 use entity::todo::Model;
+use entity::todo_comment::Model as CommentModel;
+use entity::todo_dependency::Model as DependencyModel;
+use entity::todo_tombstone::Model as TombstoneModel;
+use sea_orm::DbErr;
 
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
 use crate::common::error::{ServiceError, ServiceResult};
 
-use super::repository::TodoRepository;
+use super::model::ClientChange;
+use super::repository::{NewTodo, SyncOutcome, TodoChanges, TodoFilter, TodoRepository};
+use super::status::{self, StatusTransitions};
+use super::view::{Embedded, Includes};
+
+const DEFAULT_SYNC_LIMIT: u64 = 500;
 
 #[derive(Clone)]
 pub struct TodoService<R: TodoRepository> {
     pub repo: Arc<R>,
+    pub transitions: Arc<StatusTransitions>,
+    /// Maximum description length in characters.
+    pub description_max_length: usize,
+}
+
+/// An interactive edit; `status` and `completed` are resolved against the
+/// current status before the transition rules are checked.
+#[derive(Debug)]
+pub struct TodoUpdate {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<TodoStatus>,
+    pub completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<TodoPriority>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// Changes after a sync token, ordered by change sequence.
+#[derive(Debug)]
+pub struct ChangeSet {
+    pub upserts: Vec<Model>,
+    pub tombstones: Vec<TombstoneModel>,
+    pub next_token: i64,
+    pub has_more: bool,
 }
 
 impl<R: TodoRepository> TodoService<R> {
-    pub fn new(repo: Arc<R>) -> Self {
-        TodoService { repo }
+    pub fn new(
+        repo: Arc<R>,
+        transitions: Arc<StatusTransitions>,
+        description_max_length: usize,
+    ) -> Self {
+        TodoService {
+            repo,
+            transitions,
+            description_max_length,
+        }
     }
 
     pub async fn get_todo_by_id(&self, id: Uuid) -> ServiceResult<Model> {
         self.repo.get_by_id(id).await.map_err(ServiceError::from)
     }
 
-    pub async fn get_all_todos(&self) -> ServiceResult<Vec<Model>> {
-        self.repo.get_all().await.map_err(ServiceError::from)
+    /// Looks up a todo by its number among the todos of `owner_id`.
+    pub async fn get_todo_by_number(
+        &self,
+        owner_id: Option<String>,
+        number: i32,
+    ) -> ServiceResult<Model> {
+        self.repo
+            .get_by_number(owner_id, number)
+            .await
+            .map_err(ServiceError::from)
     }
 
-    pub async fn create_todo(&self, title: String) -> ServiceResult<Model> {
-        self.repo.create(title).await.map_err(ServiceError::from)
+    pub async fn get_all_todos(&self, filter: TodoFilter) -> ServiceResult<Vec<Model>> {
+        self.repo.get_all(&filter).await.map_err(ServiceError::from)
     }
 
-    pub async fn update_todo(
+    pub async fn get_todo_page(
         &self,
-        id: Uuid,
-        title: String,
-        completed: bool,
-    ) -> ServiceResult<Model> {
+        filter: TodoFilter,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> ServiceResult<Vec<Model>> {
         self.repo
-            .update(id, title, completed)
+            .get_page(&filter, limit, offset)
             .await
             .map_err(ServiceError::from)
     }
 
+    pub async fn count_todos(&self, filter: TodoFilter) -> ServiceResult<u64> {
+        self.repo.count(&filter).await.map_err(ServiceError::from)
+    }
+
+    /// Streams the todos matching `filter` without holding them in memory.
+    pub fn stream_todos(
+        &self,
+        filter: TodoFilter,
+    ) -> impl Stream<Item = ServiceResult<Model>> + Send + 'static {
+        self.repo
+            .stream_all(&filter)
+            .map(|row| row.map_err(ServiceError::from))
+    }
+
+    pub async fn create_todo(&self, todo: NewTodo) -> ServiceResult<Model> {
+        self.check_description(todo.description.as_deref())?;
+        self.repo.create(todo).await.map_err(ServiceError::from)
+    }
+
+    pub async fn update_todo(&self, id: Uuid, update: TodoUpdate) -> ServiceResult<Model> {
+        self.check_description(update.description.as_deref())?;
+        let current = self.repo.get_by_id(id).await.map_err(ServiceError::from)?;
+        let target = status::resolve(update.status, update.completed, current.status);
+
+        if !self.transitions.is_allowed(current.status, target) {
+            return Err(ServiceError::Conflict("status transition not allowed"));
+        }
+
+        if target == TodoStatus::Done && current.status != TodoStatus::Done {
+            let blockers = self
+                .repo
+                .get_blockers(id)
+                .await
+                .map_err(ServiceError::from)?;
+            if blockers.iter().any(is_open) {
+                return Err(ServiceError::Conflict("todo is blocked by open todos"));
+            }
+        }
+
+        let changes = TodoChanges {
+            title: update.title,
+            description: update.description,
+            status: target,
+            project_id: update.project_id,
+            due_at: update.due_at,
+            priority: update.priority,
+            rearm_reminder: current.remind_at.map(|at| at.to_utc()) != update.remind_at,
+            remind_at: update.remind_at,
+            tags: update.tags,
+            expected_status: current.status,
+        };
+        self.repo
+            .update(id, changes)
+            .await
+            .map_err(|err| match err {
+                DbErr::RecordNotUpdated => {
+                    ServiceError::Conflict("todo was changed by another request")
+                }
+                err => ServiceError::from(err),
+            })
+    }
+
+    /// Fails with `NotFound` unless `owner_id` owns the todo, so other users'
+    /// todos look the same as missing ones.
+    pub async fn check_owner(&self, id: Uuid, owner_id: &str) -> ServiceResult<()> {
+        let todo = self.repo.get_by_id(id).await.map_err(ServiceError::from)?;
+        if todo.owner_id.as_deref() != Some(owner_id) {
+            return Err(ServiceError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn check_description(&self, description: Option<&str>) -> ServiceResult<()> {
+        match description {
+            Some(text) if text.chars().count() > self.description_max_length => {
+                Err(ServiceError::ValidationError)
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub async fn delete_todo(&self, id: Uuid) -> ServiceResult<()> {
         self.repo.delete(id).await.map_err(ServiceError::from)
     }
+
+    /// Archives todos completed and last changed more than `retention` ago,
+    /// `batch_size` rows per transaction, and returns how many were archived.
+    pub async fn archive_completed(
+        &self,
+        retention: Duration,
+        batch_size: u64,
+    ) -> ServiceResult<u64> {
+        let cutoff = Utc::now() - retention;
+        let mut total = 0;
+
+        loop {
+            let archived = self
+                .repo
+                .archive_completed(cutoff, batch_size)
+                .await
+                .map_err(ServiceError::from)?;
+
+            total += archived;
+            if archived < batch_size {
+                return Ok(total);
+            }
+        }
+    }
+
+    pub async fn unarchive_todo(&self, id: Uuid) -> ServiceResult<Model> {
+        self.repo.unarchive(id).await.map_err(ServiceError::from)
+    }
+
+    /// Creates a parent todo and its ordered subtasks in one transaction.
+    /// Subtasks share the parent's owner and project.
+    pub async fn create_with_subtasks(
+        &self,
+        parent: NewTodo,
+        subtasks: Vec<String>,
+    ) -> ServiceResult<(Model, Vec<Model>)> {
+        self.repo
+            .create_with_subtasks(parent, subtasks)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn get_subtasks(&self, parent_id: Uuid) -> ServiceResult<Vec<Model>> {
+        self.get_todo_by_id(parent_id).await?;
+        self.repo
+            .get_subtasks(parent_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Loads the relations named in `include` for `todos` in one query each.
+    pub async fn load_embedded(
+        &self,
+        todos: &[Model],
+        include: Includes,
+    ) -> ServiceResult<Embedded> {
+        let ids: Vec<Uuid> = todos.iter().map(|todo| todo.id).collect();
+        let mut embedded = Embedded::default();
+        if ids.is_empty() {
+            return Ok(embedded);
+        }
+
+        if include.tags {
+            embedded.tags = self.tags_of(ids.clone()).await?;
+        }
+        if include.subtasks {
+            embedded.subtasks = self.subtasks_of(ids).await?;
+        }
+        Ok(embedded)
+    }
+
+    /// Tags of each of `ids`, sorted; todos without tags are left out.
+    pub async fn tags_of(&self, ids: Vec<Uuid>) -> ServiceResult<HashMap<Uuid, Vec<String>>> {
+        let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for tag in self
+            .repo
+            .get_tags_of(ids)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            tags.entry(tag.todo_id).or_default().push(tag.tag);
+        }
+        Ok(tags)
+    }
+
+    /// Ordered subtasks of each of `parent_ids`; todos without subtasks are
+    /// left out.
+    pub async fn subtasks_of(
+        &self,
+        parent_ids: Vec<Uuid>,
+    ) -> ServiceResult<HashMap<Uuid, Vec<Model>>> {
+        let mut subtasks: HashMap<Uuid, Vec<Model>> = HashMap::new();
+        for subtask in self
+            .repo
+            .get_subtasks_of(parent_ids)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            if let Some(parent_id) = subtask.parent_id {
+                subtasks.entry(parent_id).or_default().push(subtask);
+            }
+        }
+        Ok(subtasks)
+    }
+
+    pub async fn get_blockers(&self, id: Uuid) -> ServiceResult<Vec<Model>> {
+        self.repo.get_by_id(id).await.map_err(ServiceError::from)?;
+        self.repo.get_blockers(id).await.map_err(ServiceError::from)
+    }
+
+    /// Records that `todo_id` cannot be completed before `blocked_by_id`,
+    /// rejecting edges that would close a cycle.
+    pub async fn add_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> ServiceResult<()> {
+        self.repo
+            .get_by_id(todo_id)
+            .await
+            .map_err(ServiceError::from)?;
+        self.repo
+            .get_by_id(blocked_by_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let added = self
+            .repo
+            .add_dependency(todo_id, blocked_by_id)
+            .await
+            .map_err(ServiceError::from)?;
+        if !added {
+            return Err(ServiceError::Conflict("dependency would create a cycle"));
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_dependency(&self, todo_id: Uuid, blocked_by_id: Uuid) -> ServiceResult<()> {
+        self.repo
+            .remove_dependency(todo_id, blocked_by_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Sets or clears the assignee; `actor_id` is who made the change and
+    /// must own the todo or be its current assignee.
+    pub async fn assign_todo(
+        &self,
+        id: Uuid,
+        assignee_id: Option<String>,
+        actor_id: String,
+    ) -> ServiceResult<Model> {
+        self.repo
+            .assign(id, assignee_id, actor_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn add_comment(
+        &self,
+        todo_id: Uuid,
+        author_id: String,
+        body: String,
+    ) -> ServiceResult<CommentModel> {
+        self.repo
+            .add_comment(todo_id, author_id, body)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn list_comments(&self, todo_id: Uuid) -> ServiceResult<Vec<CommentModel>> {
+        self.get_todo_by_id(todo_id).await?;
+        self.repo
+            .list_comments(todo_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Lists the active todos of a project so that every todo comes after
+    /// the todos blocking it.
+    pub async fn get_project_todos(&self, project_id: Uuid) -> ServiceResult<Vec<Model>> {
+        let (todos, dependencies) = self
+            .repo
+            .get_by_project(project_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(topological_order(todos, &dependencies))
+    }
+
+    pub async fn pull_changes(
+        &self,
+        owner_id: &str,
+        since: i64,
+        limit: Option<u64>,
+    ) -> ServiceResult<ChangeSet> {
+        let limit = limit.unwrap_or(DEFAULT_SYNC_LIMIT);
+        let (todos, tombstones) = self
+            .repo
+            .changes_since(owner_id, since, limit + 1)
+            .await
+            .map_err(ServiceError::from)?;
+
+        Ok(merge_changes(todos, tombstones, since, limit))
+    }
+
+    /// Applies client changes under last-writer-wins. Statuses are replayed
+    /// as-is: transition rules guard interactive edits, not replication. A
+    /// todo with open blockers is still never completed. Changes only reach
+    /// `owner_id`'s todos; new ones are created for them.
+    pub async fn push_changes(
+        &self,
+        owner_id: &str,
+        changes: Vec<ClientChange>,
+    ) -> ServiceResult<Vec<(Uuid, SyncOutcome)>> {
+        let mut results = Vec::with_capacity(changes.len());
+
+        for change in changes {
+            let outcome = if change.deleted {
+                self.repo
+                    .delete_if_newer(owner_id, change.id, change.updated_at)
+                    .await
+            } else {
+                self.repo
+                    .upsert_if_newer(
+                        owner_id,
+                        change.id,
+                        change.title,
+                        change.status.map(Into::into),
+                        change.completed,
+                        change.updated_at,
+                    )
+                    .await
+            }
+            .map_err(ServiceError::from)?;
+
+            results.push((change.id, outcome));
+        }
+
+        Ok(results)
+    }
+}
+
+fn is_open(todo: &Model) -> bool {
+    !matches!(todo.status, TodoStatus::Done | TodoStatus::Cancelled)
+}
+
+/// Kahn's algorithm over `dependencies`, keeping the incoming order among
+/// todos that are ready at the same time. Edges to todos outside the list
+/// are ignored.
+fn topological_order(todos: Vec<Model>, dependencies: &[DependencyModel]) -> Vec<Model> {
+    let index: HashMap<Uuid, usize> = todos
+        .iter()
+        .enumerate()
+        .map(|(i, todo)| (todo.id, i))
+        .collect();
+    let mut pending = vec![0usize; todos.len()];
+    let mut unblocks: Vec<Vec<usize>> = vec![Vec::new(); todos.len()];
+
+    for dependency in dependencies {
+        if let (Some(&todo), Some(&blocker)) = (
+            index.get(&dependency.todo_id),
+            index.get(&dependency.blocked_by_id),
+        ) {
+            pending[todo] += 1;
+            unblocks[blocker].push(todo);
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..todos.len())
+        .filter(|&i| pending[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(todos.len());
+
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &next in &unblocks[i] {
+            pending[next] -= 1;
+            if pending[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    // Cycles are rejected on insert; anything left over still gets listed.
+    order.extend((0..todos.len()).filter(|&i| pending[i] > 0));
+
+    let mut slots: Vec<Option<Model>> = todos.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// Interleaves both change streams by sequence and keeps the first `limit`,
+/// so the returned token never skips past an unsent change.
+fn merge_changes(
+    todos: Vec<Model>,
+    tombstones: Vec<TombstoneModel>,
+    since: i64,
+    limit: u64,
+) -> ChangeSet {
+    let mut todos = todos.into_iter().peekable();
+    let mut tombstones = tombstones.into_iter().peekable();
+    let mut set = ChangeSet {
+        upserts: Vec::new(),
+        tombstones: Vec::new(),
+        next_token: since,
+        has_more: false,
+    };
+
+    loop {
+        let take_todo = match (todos.peek(), tombstones.peek()) {
+            (Some(t), Some(d)) => t.change_seq < d.change_seq,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+
+        if (set.upserts.len() + set.tombstones.len()) as u64 == limit {
+            set.has_more = true;
+            break;
+        }
+
+        if take_todo {
+            let todo = todos.next().expect("peeked");
+            set.next_token = todo.change_seq;
+            set.upserts.push(todo);
+        } else {
+            let tombstone = tombstones.next().expect("peeked");
+            set.next_token = tombstone.change_seq;
+            set.tombstones.push(tombstone);
+        }
+    }
+
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::repository::TodoRepositoryImpl;
+    use chrono::Utc;
+    use sea_orm::DatabaseConnection;
+
+    fn todo(change_seq: i64) -> Model {
+        let now = Utc::now().fixed_offset();
+        Model {
+            id: Uuid::new_v4(),
+            title: "todo".to_string(),
+            status: TodoStatus::Todo,
+            created_at: now,
+            updated_at: now,
+            change_seq,
+            completed_at: None,
+            archived_at: None,
+            project_id: None,
+            owner_id: None,
+            due_at: None,
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: None,
+            remind_at: None,
+            reminded_at: None,
+            parent_id: None,
+            position: None,
+            description: None,
+            number: 1,
+        }
+    }
+
+    fn dependency(todo: &Model, blocked_by: &Model) -> DependencyModel {
+        DependencyModel {
+            todo_id: todo.id,
+            blocked_by_id: blocked_by.id,
+            created_at: Utc::now().fixed_offset(),
+        }
+    }
+
+    fn ids(todos: &[Model]) -> Vec<Uuid> {
+        todos.iter().map(|todo| todo.id).collect()
+    }
+
+    fn tombstone(change_seq: i64) -> TombstoneModel {
+        TombstoneModel {
+            id: Uuid::new_v4(),
+            change_seq,
+            deleted_at: Utc::now().fixed_offset(),
+            owner_id: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_changes_interleaves_by_sequence() {
+        let set = merge_changes(
+            vec![todo(1), todo(4)],
+            vec![tombstone(2), tombstone(3)],
+            0,
+            10,
+        );
+
+        assert_eq!(set.upserts.len(), 2);
+        assert_eq!(set.tombstones.len(), 2);
+        assert_eq!(set.next_token, 4);
+        assert!(!set.has_more);
+    }
+
+    #[test]
+    fn test_merge_changes_stops_at_limit() {
+        let set = merge_changes(
+            vec![todo(1), todo(4)],
+            vec![tombstone(2), tombstone(3)],
+            0,
+            2,
+        );
+
+        assert_eq!(set.upserts.len(), 1);
+        assert_eq!(set.tombstones.len(), 1);
+        assert_eq!(set.next_token, 2);
+        assert!(set.has_more);
+    }
+
+    #[test]
+    fn test_topological_order_puts_blockers_first() {
+        let (a, b, c) = (todo(1), todo(2), todo(3));
+        let deps = vec![dependency(&a, &c), dependency(&b, &a)];
+
+        let ordered = topological_order(vec![a.clone(), b.clone(), c.clone()], &deps);
+
+        assert_eq!(ids(&ordered), vec![c.id, a.id, b.id]);
+    }
+
+    #[test]
+    fn test_topological_order_keeps_input_order_for_independent_todos() {
+        let (a, b, c) = (todo(1), todo(2), todo(3));
+        let deps = vec![dependency(&a, &b)];
+
+        let ordered = topological_order(vec![a.clone(), b.clone(), c.clone()], &deps);
+
+        assert_eq!(ids(&ordered), vec![b.id, a.id, c.id]);
+    }
+
+    #[test]
+    fn test_topological_order_lists_todos_left_in_a_cycle() {
+        let (a, b) = (todo(1), todo(2));
+        let deps = vec![dependency(&a, &b), dependency(&b, &a)];
+
+        let ordered = topological_order(vec![a.clone(), b.clone()], &deps);
+
+        assert_eq!(ids(&ordered), vec![a.id, b.id]);
+    }
+
+    #[test]
+    fn test_description_limit_is_configurable() {
+        let service = TodoService::new(
+            Arc::new(TodoRepositoryImpl {
+                db: DatabaseConnection::Disconnected,
+            }),
+            Arc::new(StatusTransitions::parse("").unwrap()),
+            5,
+        );
+
+        assert!(service.check_description(None).is_ok());
+        assert!(service.check_description(Some("héllo")).is_ok());
+        assert!(matches!(
+            service.check_description(Some("héllo!")),
+            Err(ServiceError::ValidationError)
+        ));
+    }
+
+    #[test]
+    fn test_merge_changes_empty_keeps_token() {
+        let set = merge_changes(vec![], vec![], 42, 10);
+
+        assert_eq!(set.next_token, 42);
+        assert!(!set.has_more);
+    }
 }
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use entity::sea_orm_active_enums::TodoStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Transitions used when `TODO_STATUS_TRANSITIONS` is not set.
+pub const DEFAULT_TRANSITIONS: &str = "todo:in_progress,blocked,done,cancelled;\
+in_progress:todo,blocked,done,cancelled;\
+blocked:todo,in_progress,cancelled;\
+done:todo,in_progress;\
+cancelled:todo";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoState {
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+impl From<TodoStatus> for TodoState {
+    fn from(status: TodoStatus) -> Self {
+        match status {
+            TodoStatus::Todo => TodoState::Todo,
+            TodoStatus::InProgress => TodoState::InProgress,
+            TodoStatus::Blocked => TodoState::Blocked,
+            TodoStatus::Done => TodoState::Done,
+            TodoStatus::Cancelled => TodoState::Cancelled,
+        }
+    }
+}
+
+impl From<TodoState> for TodoStatus {
+    fn from(state: TodoState) -> Self {
+        match state {
+            TodoState::Todo => TodoStatus::Todo,
+            TodoState::InProgress => TodoStatus::InProgress,
+            TodoState::Blocked => TodoStatus::Blocked,
+            TodoState::Done => TodoStatus::Done,
+            TodoState::Cancelled => TodoStatus::Cancelled,
+        }
+    }
+}
+
+impl FromStr for TodoState {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_status(value).map(Into::into)
+    }
+}
+
+/// Allowed status changes, parsed from `from:to,to;from:to` rules.
+/// Keeping the current status is always allowed.
+#[derive(Debug, Clone)]
+pub struct StatusTransitions {
+    allowed: HashMap<TodoStatus, HashSet<TodoStatus>>,
+}
+
+impl StatusTransitions {
+    pub fn parse(rules: &str) -> Result<Self, String> {
+        let mut allowed: HashMap<TodoStatus, HashSet<TodoStatus>> = HashMap::new();
+
+        for rule in rules.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+            let (from, targets) = rule
+                .split_once(':')
+                .ok_or_else(|| format!("invalid transition rule `{rule}`"))?;
+            let from = parse_status(from)?;
+
+            for to in targets.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                allowed.entry(from).or_default().insert(parse_status(to)?);
+            }
+        }
+
+        Ok(Self { allowed })
+    }
+
+    pub fn is_allowed(&self, from: TodoStatus, to: TodoStatus) -> bool {
+        from == to
+            || self
+                .allowed
+                .get(&from)
+                .is_some_and(|targets| targets.contains(&to))
+    }
+}
+
+fn parse_status(value: &str) -> Result<TodoStatus, String> {
+    match value.trim() {
+        "todo" => Ok(TodoStatus::Todo),
+        "in_progress" => Ok(TodoStatus::InProgress),
+        "blocked" => Ok(TodoStatus::Blocked),
+        "done" => Ok(TodoStatus::Done),
+        "cancelled" => Ok(TodoStatus::Cancelled),
+        other => Err(format!("unknown todo status `{other}`")),
+    }
+}
+
+/// Picks the status for a write that may carry an explicit `status`, the
+/// legacy `completed` flag, or neither. Clearing `completed` only reopens
+/// todos that are done and leaves other states untouched.
+pub fn resolve(
+    requested: Option<TodoStatus>,
+    completed: Option<bool>,
+    current: TodoStatus,
+) -> TodoStatus {
+    match (requested, completed) {
+        (Some(status), _) => status,
+        (None, Some(true)) => TodoStatus::Done,
+        (None, Some(false)) if current == TodoStatus::Done => TodoStatus::Todo,
+        _ => current,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_transitions_parse() {
+        let transitions = StatusTransitions::parse(DEFAULT_TRANSITIONS).unwrap();
+
+        assert!(transitions.is_allowed(TodoStatus::Todo, TodoStatus::InProgress));
+        assert!(transitions.is_allowed(TodoStatus::Done, TodoStatus::Todo));
+        assert!(!transitions.is_allowed(TodoStatus::Blocked, TodoStatus::Done));
+        assert!(!transitions.is_allowed(TodoStatus::Cancelled, TodoStatus::Done));
+    }
+
+    #[test]
+    fn test_same_status_is_always_allowed() {
+        let transitions = StatusTransitions::parse("").unwrap();
+
+        assert!(transitions.is_allowed(TodoStatus::Blocked, TodoStatus::Blocked));
+        assert!(!transitions.is_allowed(TodoStatus::Todo, TodoStatus::Done));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_status() {
+        assert!(StatusTransitions::parse("todo:finished").is_err());
+        assert!(StatusTransitions::parse("todo").is_err());
+    }
+
+    #[test]
+    fn test_resolve_status() {
+        use TodoStatus::*;
+
+        let cases = vec![
+            (Some(Blocked), Some(true), Todo, Blocked),
+            (None, Some(true), InProgress, Done),
+            (None, Some(false), Done, Todo),
+            (None, Some(false), InProgress, InProgress),
+            (None, None, Blocked, Blocked),
+        ];
+
+        for (requested, completed, current, expected) in cases {
+            assert_eq!(resolve(requested, completed, current), expected);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, FixedOffset};
+use entity::todo::Model;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::common::serialization::timestamp;
+use crate::todo::model::Priority;
+use crate::todo::status::TodoState;
+
+/// The v2 todo representation. Unlike v1 it carries creation and update
+/// timestamps and links, and drops the legacy `completed` flag.
+#[derive(Serialize)]
+pub struct TodoV2 {
+    pub id: Uuid,
+    pub number: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TodoState,
+    pub priority: Option<Priority>,
+    pub project_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub position: Option<i32>,
+    pub assignee_id: Option<String>,
+    #[serde(serialize_with = "timestamp")]
+    pub due_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub remind_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub reminded_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub completed_at: Option<DateTime<FixedOffset>>,
+    #[serde(serialize_with = "timestamp")]
+    pub archived_at: Option<DateTime<FixedOffset>>,
+    pub tracked_seconds: i64,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub updated_at: DateTime<FixedOffset>,
+    pub links: TodoLinks,
+}
+
+#[derive(Serialize)]
+pub struct TodoLinks {
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub subtasks: String,
+    pub comments: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+impl From<Model> for TodoV2 {
+    fn from(model: Model) -> Self {
+        let href = |id: Uuid| format!("/api/v2/todos/{id}");
+        let links = TodoLinks {
+            self_: href(model.id),
+            subtasks: format!("{}/subtasks", href(model.id)),
+            comments: format!("/api/v1/todos/{}/comments", model.id),
+            parent: model.parent_id.map(href),
+        };
+
+        Self {
+            id: model.id,
+            number: model.number,
+            title: model.title,
+            description: model.description,
+            status: model.status.into(),
+            priority: model.priority.map(Into::into),
+            project_id: model.project_id,
+            parent_id: model.parent_id,
+            position: model.position,
+            assignee_id: model.assignee_id,
+            due_at: model.due_at,
+            remind_at: model.remind_at,
+            reminded_at: model.reminded_at,
+            completed_at: model.completed_at,
+            archived_at: model.archived_at,
+            tracked_seconds: model.tracked_seconds,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            links,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::envelope::Envelope;
+    use chrono::Utc;
+    use entity::sea_orm_active_enums::TodoStatus;
+    use serde_json::json;
+
+    #[test]
+    fn test_list_envelope_links_and_timestamps() {
+        let now = Utc::now().fixed_offset();
+        let parent_id = Uuid::new_v4();
+        let model = Model {
+            id: Uuid::new_v4(),
+            title: "Write changelog".to_string(),
+            status: TodoStatus::Todo,
+            created_at: now,
+            updated_at: now,
+            change_seq: 0,
+            completed_at: None,
+            archived_at: None,
+            project_id: None,
+            owner_id: None,
+            due_at: None,
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: None,
+            remind_at: None,
+            reminded_at: None,
+            parent_id: Some(parent_id),
+            position: Some(0),
+            description: None,
+            number: 1,
+        };
+        let id = model.id;
+
+        let body = serde_json::to_value(Envelope::list(vec![TodoV2::from(model)])).unwrap();
+
+        assert_eq!(body["meta"], json!({ "count": 1 }));
+        assert_eq!(
+            body["data"][0]["links"]["self"],
+            format!("/api/v2/todos/{id}")
+        );
+        assert_eq!(
+            body["data"][0]["links"]["parent"],
+            format!("/api/v2/todos/{parent_id}")
+        );
+        assert!(body["data"][0]["created_at"].is_string());
+        assert!(body["data"][0].get("completed").is_none());
+    }
+}
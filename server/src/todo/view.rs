@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use entity::todo::Model;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use uuid::Uuid;
+
+use super::model::{RenderFormat, TodoResponse};
+use crate::common::serialization::{to_snake_case, Timestamp};
+
+/// Declares [`TODO_FIELDS`] and writes one of them from a [`TodoResponse`],
+/// from a single list. `as Wrapper` writes the field through `Wrapper`, as
+/// `serialize_with` does on the struct.
+macro_rules! todo_fields {
+    (@value $value:expr) => { $value };
+    (@value $value:expr, $with:ident) => { &$with($value) };
+    ($($field:ident $(as $with:ident)?),* $(,)?) => {
+        /// Every field of a serialized [`TodoResponse`], as accepted by `?fields=`.
+        pub const TODO_FIELDS: &[&str] = &[$(stringify!($field)),*];
+
+        fn serialize_field<S: SerializeStruct>(
+            todo: &TodoResponse,
+            field: &'static str,
+            state: &mut S,
+        ) -> Result<(), S::Error> {
+            $(
+                if field == stringify!($field) {
+                    return state.serialize_field(
+                        field,
+                        todo_fields!(@value &todo.$field $(, $with)?),
+                    );
+                }
+            )*
+            state.skip_field(field)
+        }
+    };
+}
+
+todo_fields![
+    id,
+    number,
+    title,
+    description,
+    description_html,
+    status,
+    completed,
+    completed_at as Timestamp,
+    archived_at as Timestamp,
+    project_id,
+    due_at as Timestamp,
+    priority,
+    tracked_seconds,
+    assignee_id,
+    remind_at as Timestamp,
+    reminded_at as Timestamp,
+    parent_id,
+    position,
+];
+
+/// Relations that `?include=` embeds into each todo.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Includes {
+    pub tags: bool,
+    pub subtasks: bool,
+}
+
+/// Relations loaded for a page of todos, keyed by todo id.
+#[derive(Debug, Default)]
+pub struct Embedded {
+    pub tags: HashMap<Uuid, Vec<String>>,
+    pub subtasks: HashMap<Uuid, Vec<Model>>,
+}
+
+/// How todos are turned into responses, from `?render=`, `?fields=` and
+/// `?include=`.
+#[derive(Debug, Clone, Default)]
+pub struct TodoView {
+    pub render: Option<RenderFormat>,
+    /// Fields to keep; `None` keeps all of them.
+    pub fields: Option<Vec<&'static str>>,
+    pub include: Includes,
+}
+
+impl TodoView {
+    pub fn parse(
+        render: Option<RenderFormat>,
+        fields: Option<&str>,
+        include: Option<&str>,
+    ) -> Result<Self, String> {
+        let fields = fields
+            .map(|fields| {
+                split_list(fields)
+                    .map(|name| {
+                        // camelCase names are accepted for clients using that
+                        // `JSON_FIELD_NAMING`.
+                        let snake = to_snake_case(name);
+                        TODO_FIELDS
+                            .iter()
+                            .find(|field| **field == snake)
+                            .copied()
+                            .ok_or_else(|| format!("unknown field `{name}`"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        if render != Some(RenderFormat::Html)
+            && fields
+                .as_ref()
+                .is_some_and(|fields| fields.contains(&"description_html"))
+        {
+            return Err("`description_html` requires `render=html`".to_string());
+        }
+
+        let mut includes = Includes::default();
+        for name in split_list(include.unwrap_or_default()) {
+            match name {
+                "tags" => includes.tags = true,
+                "subtasks" => includes.subtasks = true,
+                other => return Err(format!("unknown include `{other}`")),
+            }
+        }
+
+        Ok(Self {
+            render,
+            fields,
+            include: includes,
+        })
+    }
+
+    /// Projects `todo` and appends the included relations; embedded
+    /// subtasks get the same projection.
+    pub fn shape(&self, todo: Model, embedded: &Embedded) -> ShapedTodo {
+        let id = todo.id;
+        let mut shaped = self.project(todo);
+
+        if self.include.tags {
+            let tags = embedded.tags.get(&id).cloned().unwrap_or_default();
+            shaped.tags = Some(tags);
+        }
+        if self.include.subtasks {
+            let subtasks = embedded
+                .subtasks
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .map(|subtask| self.project(subtask.clone()))
+                .collect();
+            shaped.subtasks = Some(subtasks);
+        }
+        shaped
+    }
+
+    fn project(&self, todo: Model) -> ShapedTodo {
+        let todo = TodoResponse::from(todo).rendered(self.render);
+        // `description_html` is only written when rendered, like on the
+        // full response.
+        let fields = TODO_FIELDS
+            .iter()
+            .filter(|field| self.fields.as_ref().is_none_or(|keep| keep.contains(field)))
+            .filter(|field| **field != "description_html" || todo.description_html.is_some())
+            .copied()
+            .collect();
+        ShapedTodo {
+            todo,
+            fields,
+            tags: None,
+            subtasks: None,
+        }
+    }
+}
+
+/// A todo response after projection and embedding.
+#[derive(Debug)]
+pub struct ShapedTodo {
+    pub todo: TodoResponse,
+    /// The fields to write, in `TODO_FIELDS` order.
+    pub fields: Vec<&'static str>,
+    pub tags: Option<Vec<String>>,
+    pub subtasks: Option<Vec<ShapedTodo>>,
+}
+
+/// A struct rather than a map, so the response's field naming applies.
+impl Serialize for ShapedTodo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = self.fields.len()
+            + usize::from(self.tags.is_some())
+            + usize::from(self.subtasks.is_some());
+        let mut state = serializer.serialize_struct("TodoResponse", len)?;
+        for field in &self.fields {
+            serialize_field(&self.todo, field, &mut state)?;
+        }
+        if let Some(tags) = &self.tags {
+            state.serialize_field("tags", tags)?;
+        }
+        if let Some(subtasks) = &self.subtasks {
+            state.serialize_field("subtasks", subtasks)?;
+        }
+        state.end()
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use entity::sea_orm_active_enums::TodoStatus;
+    use serde_json::Value;
+
+    fn todo(title: &str, parent_id: Option<Uuid>) -> Model {
+        let now = Utc::now().fixed_offset();
+        Model {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            status: TodoStatus::Todo,
+            created_at: now,
+            updated_at: now,
+            change_seq: 0,
+            completed_at: None,
+            archived_at: None,
+            project_id: None,
+            owner_id: None,
+            due_at: None,
+            priority: None,
+            tracked_seconds: 0,
+            assignee_id: None,
+            remind_at: None,
+            reminded_at: None,
+            parent_id,
+            position: parent_id.map(|_| 0),
+            description: None,
+            number: 1,
+        }
+    }
+
+    #[test]
+    fn test_todo_fields_match_the_response() {
+        let mut response = TodoResponse::from(todo("Write", None));
+        response.description_html = Some(String::new());
+        let json = serde_json::to_string(&response).unwrap();
+        let Value::Object(map) = serde_json::from_str(&json).unwrap() else {
+            panic!("todo response is not an object");
+        };
+        let positions: Vec<usize> = TODO_FIELDS
+            .iter()
+            .map(|field| json.find(&format!("\"{field}\":")).unwrap())
+            .collect();
+
+        assert_eq!(map.len(), TODO_FIELDS.len());
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_shaped_todo_follows_the_json_policy() {
+        use crate::common::serialization::{FieldNaming, JsonPolicy, TimestampPrecision};
+
+        let view = TodoView::parse(None, Some("title,due_at"), None).unwrap();
+        let mut todo = todo("Write", None);
+        todo.due_at = chrono::DateTime::parse_from_rfc3339("2025-03-01T09:30:00.123456Z").ok();
+        let shaped = view.shape(todo, &Embedded::default());
+        let policy = JsonPolicy {
+            naming: FieldNaming::CamelCase,
+            timestamps: TimestampPrecision::Millis,
+        };
+
+        let json: Value = serde_json::from_slice(&policy.to_vec(&shaped).unwrap()).unwrap();
+        let msgpack: HashMap<String, Value> =
+            rmp_serde::from_slice(&rmp_serde::to_vec_named(&shaped).unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({ "title": "Write", "dueAt": "2025-03-01T09:30:00.123Z" })
+        );
+        assert_eq!(msgpack.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_names() {
+        assert_eq!(
+            TodoView::parse(None, Some("id,colour"), None).unwrap_err(),
+            "unknown field `colour`"
+        );
+        assert_eq!(
+            TodoView::parse(None, None, Some("tags,owner")).unwrap_err(),
+            "unknown include `owner`"
+        );
+    }
+
+    #[test]
+    fn test_parse_requires_render_for_description_html() {
+        assert_eq!(
+            TodoView::parse(None, Some("id,description_html"), None).unwrap_err(),
+            "`description_html` requires `render=html`"
+        );
+        assert!(TodoView::parse(Some(RenderFormat::Html), Some("descriptionHtml"), None).is_ok());
+    }
+
+    #[test]
+    fn test_shape_projects_fields_and_embeds_relations() {
+        let view = TodoView::parse(None, Some("id, title"), Some("tags,subtasks")).unwrap();
+        let parent = todo("Release", None);
+        let mut embedded = Embedded::default();
+        embedded.tags.insert(parent.id, vec!["ops".to_string()]);
+        embedded
+            .subtasks
+            .insert(parent.id, vec![todo("Tag", Some(parent.id))]);
+
+        let shaped = serde_json::to_value(view.shape(parent.clone(), &embedded)).unwrap();
+
+        assert_eq!(
+            shaped,
+            serde_json::json!({
+                "id": parent.id,
+                "title": "Release",
+                "tags": ["ops"],
+                "subtasks": [{ "id": embedded.subtasks[&parent.id][0].id, "title": "Tag" }],
+            })
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, NoContent},
+    Extension,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    common::error::{ApiError, ApiResult},
+    common::jwt::Claims,
+    common::negotiate::Negotiated,
+    common::state::AppState,
+    common::validated_body::ValidatedBody,
+    common::validated_path::ValidatedPath,
+    todo::model::{validate_title_length, TodoResponse},
+    todo::repository::NewTodo,
+};
+
+use super::model::{
+    InstantiateTemplateRequest, InstantiateTemplateResponse, SaveTemplateRequest, TemplateResponse,
+};
+use super::service::render;
+
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Negotiated<Vec<TemplateResponse>>> {
+    let templates = state
+        .template_service
+        .list_templates(&claims.sub)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to list templates: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(
+        templates.into_iter().map(TemplateResponse::from).collect(),
+    ))
+}
+
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(template_id): ValidatedPath<Uuid>,
+) -> ApiResult<Negotiated<TemplateResponse>> {
+    let template = state
+        .template_service
+        .get_template(&claims.sub, template_id)
+        .await
+        .map_err(|err| {
+            error!(template_id = %template_id, "Failed to get template: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TemplateResponse::from(template)))
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedBody(payload): ValidatedBody<SaveTemplateRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let template = state
+        .template_service
+        .create_template(&claims.sub, payload.title, payload.items)
+        .await
+        .map_err(|err| {
+            error!(owner_id = %claims.sub, "Failed to create template: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(TemplateResponse::from(template)),
+    ))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(template_id): ValidatedPath<Uuid>,
+    ValidatedBody(payload): ValidatedBody<SaveTemplateRequest>,
+) -> ApiResult<Negotiated<TemplateResponse>> {
+    let template = state
+        .template_service
+        .update_template(&claims.sub, template_id, payload.title, payload.items)
+        .await
+        .map_err(|err| {
+            error!(template_id = %template_id, "Failed to update template: {:?}", err);
+            err
+        })?;
+
+    Ok(Negotiated(TemplateResponse::from(template)))
+}
+
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(template_id): ValidatedPath<Uuid>,
+) -> ApiResult<NoContent> {
+    state
+        .template_service
+        .delete_template(&claims.sub, template_id)
+        .await
+        .map_err(|err| {
+            error!(template_id = %template_id, "Failed to delete template: {:?}", err);
+            err
+        })?;
+
+    Ok(NoContent)
+}
+
+/// Creates a todo from the template title and one subtask per item, all in
+/// a single transaction.
+pub async fn instantiate(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedPath(template_id): ValidatedPath<Uuid>,
+    ValidatedBody(payload): ValidatedBody<InstantiateTemplateRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let template = state
+        .template_service
+        .get_template(&claims.sub, template_id)
+        .await
+        .map_err(|err| {
+            error!(template_id = %template_id, "Failed to load template: {:?}", err);
+            err
+        })?;
+
+    let rendered = render(&template, &payload.variables).map_err(ApiError::BadRequest)?;
+    for title in std::iter::once(&rendered.title).chain(&rendered.items) {
+        validate_title_length(title).map_err(|_| {
+            ApiError::BadRequest("substituted titles must be between 1 and 255 characters".into())
+        })?;
+    }
+
+    let parent = NewTodo {
+        title: rendered.title,
+        project_id: payload.project_id,
+        owner_id: Some(claims.sub),
+        due_at: payload.due_at,
+        ..Default::default()
+    };
+    let (todo, subtasks) = state
+        .todo_service
+        .create_with_subtasks(parent, rendered.items)
+        .await
+        .map_err(|err| {
+            error!(template_id = %template_id, "Failed to instantiate template: {:?}", err);
+            err
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Negotiated(InstantiateTemplateResponse {
+            todo: TodoResponse::from(todo),
+            subtasks: subtasks.into_iter().map(TodoResponse::from).collect(),
+        }),
+    ))
+}
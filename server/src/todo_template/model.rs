@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::common::serialization::timestamp;
+use crate::todo::model::{validate_title_length, TodoResponse};
+
+use super::repository::TemplateWithItems;
+use super::substitute::placeholders;
+
+const MAX_ITEMS: usize = 100;
+
+#[derive(Serialize)]
+pub struct TemplateResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub items: Vec<String>,
+    /// Variables used by the title and items, needed to instantiate.
+    pub variables: Vec<String>,
+    #[serde(serialize_with = "timestamp")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(serialize_with = "timestamp")]
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<TemplateWithItems> for TemplateResponse {
+    fn from((template, items): TemplateWithItems) -> Self {
+        let items: Vec<String> = items.into_iter().map(|item| item.title).collect();
+
+        let mut variables: Vec<String> = Vec::new();
+        for text in std::iter::once(&template.title).chain(&items) {
+            for name in placeholders(text) {
+                if !variables.iter().any(|known| known == name) {
+                    variables.push(name.to_string());
+                }
+            }
+        }
+
+        Self {
+            id: template.id,
+            title: template.title,
+            items,
+            variables,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
+
+/// Body of both create and update; updates replace all items.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SaveTemplateRequest {
+    #[validate(custom(function = "validate_title_length"))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(custom(function = "validate_items"))]
+    pub items: Vec<String>,
+}
+
+fn validate_items(items: &[String]) -> Result<(), ValidationError> {
+    if items.len() > MAX_ITEMS {
+        return Err(ValidationError::new("must contain at most 100 items"));
+    }
+    items
+        .iter()
+        .try_for_each(|item| validate_title_length(item))
+}
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct InstantiateTemplateRequest {
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    pub project_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct InstantiateTemplateResponse {
+    pub todo: TodoResponse,
+    pub subtasks: Vec<TodoResponse>,
+}
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity::todo_template::{ActiveModel, Column, Entity, Model};
+use entity::todo_template_item::{
+    ActiveModel as ItemActiveModel, Column as ItemColumn, Entity as ItemEntity, Model as ItemModel,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+/// A template with its items in order.
+pub type TemplateWithItems = (Model, Vec<ItemModel>);
+
+#[async_trait]
+pub trait TemplateRepository: Send + Sync {
+    async fn list(&self, owner_id: &str) -> Result<Vec<TemplateWithItems>, DbErr>;
+    async fn get(&self, owner_id: &str, id: Uuid) -> Result<TemplateWithItems, DbErr>;
+    async fn create(
+        &self,
+        owner_id: &str,
+        title: String,
+        items: Vec<String>,
+    ) -> Result<TemplateWithItems, DbErr>;
+    async fn update(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        title: String,
+        items: Vec<String>,
+    ) -> Result<TemplateWithItems, DbErr>;
+    async fn delete(&self, owner_id: &str, id: Uuid) -> Result<(), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct TemplateRepositoryImpl {
+    pub db: DatabaseConnection,
+}
+
+#[async_trait]
+impl TemplateRepository for TemplateRepositoryImpl {
+    async fn list(&self, owner_id: &str) -> Result<Vec<TemplateWithItems>, DbErr> {
+        Entity::find()
+            .filter(Column::OwnerId.eq(owner_id))
+            .order_by_asc(Column::Title)
+            .order_by_asc(Column::Id)
+            .find_with_related(ItemEntity)
+            .order_by_asc(ItemColumn::Position)
+            .all(&self.db)
+            .await
+    }
+
+    async fn get(&self, owner_id: &str, id: Uuid) -> Result<TemplateWithItems, DbErr> {
+        let template = Entity::find_by_id(id)
+            .filter(Column::OwnerId.eq(owner_id))
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Template not found".to_string()))?;
+        let items = items_of(&self.db, id).await?;
+
+        Ok((template, items))
+    }
+
+    async fn create(
+        &self,
+        owner_id: &str,
+        title: String,
+        items: Vec<String>,
+    ) -> Result<TemplateWithItems, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let template = ActiveModel {
+            owner_id: Set(owner_id.to_string()),
+            title: Set(title),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        let items = insert_items(&txn, template.id, items).await?;
+
+        txn.commit().await?;
+        Ok((template, items))
+    }
+
+    async fn update(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        title: String,
+        items: Vec<String>,
+    ) -> Result<TemplateWithItems, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let template: ActiveModel = Entity::find_by_id(id)
+            .filter(Column::OwnerId.eq(owner_id))
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Template not found".to_string()))?
+            .into();
+        let template = ActiveModel {
+            title: Set(title),
+            updated_at: Set(DateTime::from(Utc::now())),
+            ..template
+        }
+        .update(&txn)
+        .await?;
+
+        ItemEntity::delete_many()
+            .filter(ItemColumn::TemplateId.eq(id))
+            .exec(&txn)
+            .await?;
+        let items = insert_items(&txn, id, items).await?;
+
+        txn.commit().await?;
+        Ok((template, items))
+    }
+
+    async fn delete(&self, owner_id: &str, id: Uuid) -> Result<(), DbErr> {
+        let res = Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::OwnerId.eq(owner_id))
+            .exec(&self.db)
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Template not found".to_string()));
+        }
+        Ok(())
+    }
+}
+
+async fn items_of<C: ConnectionTrait>(
+    conn: &C,
+    template_id: Uuid,
+) -> Result<Vec<ItemModel>, DbErr> {
+    ItemEntity::find()
+        .filter(ItemColumn::TemplateId.eq(template_id))
+        .order_by_asc(ItemColumn::Position)
+        .all(conn)
+        .await
+}
+
+async fn insert_items<C: ConnectionTrait>(
+    conn: &C,
+    template_id: Uuid,
+    titles: Vec<String>,
+) -> Result<Vec<ItemModel>, DbErr> {
+    if titles.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    ItemEntity::insert_many((0..).zip(titles).map(|(position, title)| ItemActiveModel {
+        template_id: Set(template_id),
+        position: Set(position),
+        title: Set(title),
+    }))
+    .exec_without_returning(conn)
+    .await?;
+
+    items_of(conn, template_id).await
+}
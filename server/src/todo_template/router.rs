@@ -0,0 +1,23 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::state::AppState, middleware::auth::is_authenticated, todo_template::controller,
+};
+
+pub fn init() -> Router<AppState> {
+    // Templates belong to the caller, like saved filters.
+    Router::new()
+        .route(
+            "/{template_id}",
+            get(controller::get_by_id)
+                .put(controller::update)
+                .delete(controller::delete),
+        )
+        .route("/{template_id}/instantiate", post(controller::instantiate))
+        .route("/", get(controller::list).post(controller::create))
+        .layer(middleware::from_fn(is_authenticated))
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::common::error::{ServiceError, ServiceResult};
+
+use super::repository::{TemplateRepository, TemplateWithItems};
+use super::substitute::substitute;
+
+#[derive(Clone)]
+pub struct TemplateService<R: TemplateRepository> {
+    pub repo: Arc<R>,
+}
+
+/// Titles of a template with its variables filled in.
+#[derive(Debug, PartialEq)]
+pub struct RenderedTemplate {
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+impl<R: TemplateRepository> TemplateService<R> {
+    pub fn new(repo: Arc<R>) -> Self {
+        TemplateService { repo }
+    }
+
+    pub async fn list_templates(&self, owner_id: &str) -> ServiceResult<Vec<TemplateWithItems>> {
+        self.repo.list(owner_id).await.map_err(ServiceError::from)
+    }
+
+    pub async fn get_template(&self, owner_id: &str, id: Uuid) -> ServiceResult<TemplateWithItems> {
+        self.repo
+            .get(owner_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn create_template(
+        &self,
+        owner_id: &str,
+        title: String,
+        items: Vec<String>,
+    ) -> ServiceResult<TemplateWithItems> {
+        self.repo
+            .create(owner_id, title, items)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn update_template(
+        &self,
+        owner_id: &str,
+        id: Uuid,
+        title: String,
+        items: Vec<String>,
+    ) -> ServiceResult<TemplateWithItems> {
+        self.repo
+            .update(owner_id, id, title, items)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn delete_template(&self, owner_id: &str, id: Uuid) -> ServiceResult<()> {
+        self.repo
+            .delete(owner_id, id)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+/// Substitutes `variables` into the title and every item.
+pub fn render(
+    (template, items): &TemplateWithItems,
+    variables: &HashMap<String, String>,
+) -> Result<RenderedTemplate, String> {
+    Ok(RenderedTemplate {
+        title: substitute(&template.title, variables)?,
+        items: items
+            .iter()
+            .map(|item| substitute(&item.title, variables))
+            .collect::<Result<_, _>>()?,
+    })
+}
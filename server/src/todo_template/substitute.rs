@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// Splits `text` into literal text and `{{name}}` placeholders. Names are
+/// ASCII letters, digits and underscores, optionally padded with spaces;
+/// anything else between braces is kept as text.
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(OPEN) {
+        let after_open = &rest[start + OPEN.len()..];
+        let Some(end) = after_open.find(CLOSE) else {
+            break;
+        };
+
+        let name = after_open[..end].trim();
+        let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if valid {
+            segments.push(Segment::Text(&rest[..start]));
+            segments.push(Segment::Variable(name));
+        } else {
+            segments.push(Segment::Text(
+                &rest[..start + OPEN.len() + end + CLOSE.len()],
+            ));
+        }
+        rest = &after_open[end + CLOSE.len()..];
+    }
+
+    segments.push(Segment::Text(rest));
+    segments
+}
+
+/// Placeholder names used in `text`, in order of first use.
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    for segment in segments(text) {
+        if let Segment::Variable(name) = segment {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Replaces every placeholder with its value. Fails on the first
+/// placeholder without a value.
+pub fn substitute(text: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut output = String::with_capacity(text.len());
+    for segment in segments(text) {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Variable(name) => {
+                let value = variables
+                    .get(name)
+                    .ok_or_else(|| format!("missing value for variable `{name}`"))?;
+                output.push_str(value);
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_substitute_replaces_placeholders() {
+        let vars = variables(&[("name", "Ada"), ("version", "1.2")]);
+
+        assert_eq!(
+            substitute("Onboard {{name}} for {{ version }}", &vars),
+            Ok("Onboard Ada for 1.2".to_string())
+        );
+        assert_eq!(
+            substitute("{{name}}{{name}}", &vars),
+            Ok("AdaAda".to_string())
+        );
+    }
+
+    #[test]
+    fn test_substitute_keeps_text_that_is_not_a_placeholder() {
+        let vars = variables(&[]);
+
+        for text in ["a {{ b c }} d", "open {{ only", "{{}}", "}} {{"] {
+            assert_eq!(substitute(text, &vars), Ok(text.to_string()), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_substitute_rejects_missing_variables() {
+        let result = substitute("Release {{version}}", &variables(&[]));
+
+        assert_eq!(
+            result,
+            Err("missing value for variable `version`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_placeholders_in_order_of_first_use() {
+        assert_eq!(
+            placeholders("{{b}} then {{a}} then {{ b }}"),
+            vec!["b", "a"]
+        );
+    }
+}